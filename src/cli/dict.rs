@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, HashMap};
+
+use color_eyre::{Result, eyre::bail};
+use wordguessr::service::dictionary::{Word, WordService};
+
+/// Problem entries are listed when there are at most this many of a kind
+const MAX_LISTED_PROBLEMS: usize = 20;
+/// Letters shown for each position
+const TOP_LETTERS: usize = 6;
+
+pub async fn run(
+    word_service: &dyn WordService,
+    word_lengths: &[usize],
+    max_definition_length: usize,
+) -> Result<()> {
+    let words = word_service.get_words().await?;
+    let language = word_service.language();
+
+    let mut per_length: BTreeMap<usize, usize> = BTreeMap::new();
+    for word in &words {
+        *per_length.entry(word.word.chars().count()).or_default() += 1;
+    }
+    println!("{} word(s) in {}", words.len(), language.name);
+    for (length, count) in &per_length {
+        println!("  {length:>3} letters: {count}");
+    }
+
+    for &length in word_lengths {
+        print_letter_frequencies(&words, length);
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in &words {
+        *counts.entry(&word.word).or_default() += 1;
+    }
+    let mut duplicates: Vec<&str> = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(word, _)| word)
+        .collect();
+    duplicates.sort_unstable();
+
+    let non_alphabetic = problems(&words, |w| !language.is_spelled_with_alphabet(&w.word));
+    let empty = problems(&words, |w| w.meanings.iter().all(|m| m.trim().is_empty()));
+    let too_long = problems(&words, |w| {
+        w.meanings
+            .iter()
+            .any(|m| m.chars().count() > max_definition_length)
+    });
+
+    println!("\nProblem entries:");
+    print_problems("word(s) with letters outside the alphabet", &non_alphabetic);
+    print_problems("duplicate(s) after uppercasing", &duplicates);
+    print_problems("word(s) without a definition", &empty);
+    print_problems(
+        &format!("word(s) with a definition over {max_definition_length} characters"),
+        &too_long,
+    );
+
+    let missing: Vec<String> = word_lengths
+        .iter()
+        .filter(|length| !per_length.contains_key(length))
+        .map(usize::to_string)
+        .collect();
+    if !missing.is_empty() {
+        bail!("No word available for length {}", missing.join(", "));
+    }
+
+    Ok(())
+}
+
+fn problems(words: &[Word], is_problem: impl Fn(&Word) -> bool) -> Vec<&str> {
+    let mut found: Vec<&str> = words
+        .iter()
+        .filter(|w| is_problem(w))
+        .map(|w| w.word.as_str())
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+fn print_problems(kind: &str, words: &[&str]) {
+    println!("  {} {kind}", words.len());
+    if !words.is_empty() && words.len() <= MAX_LISTED_PROBLEMS {
+        println!("    {}", words.join(", "));
+    }
+}
+
+/// Prints the most common letters at each position of the words of `length`
+fn print_letter_frequencies(words: &[Word], length: usize) {
+    let words: Vec<Vec<char>> = words
+        .iter()
+        .map(|w| w.word.chars().collect::<Vec<char>>())
+        .filter(|letters| letters.len() == length)
+        .collect();
+    if words.is_empty() {
+        return;
+    }
+
+    println!("\nLetter frequencies by position for {length} letters:");
+    for position in 0..length {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for letters in &words {
+            *counts.entry(letters[position]).or_default() += 1;
+        }
+        let mut counts: Vec<(char, usize)> = counts.into_iter().collect();
+        counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let top: Vec<String> = counts
+            .iter()
+            .take(TOP_LETTERS)
+            .map(|(letter, count)| {
+                format!(
+                    "{letter} {:>4.1}%",
+                    *count as f64 * 100.0 / words.len() as f64
+                )
+            })
+            .collect();
+        println!("  {:>3}: {}", position + 1, top.join("  "));
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+
+use color_eyre::Result;
+use wordguessr::service::{
+    dictionary::{Word, WordService},
+    layered::{LayeredWordService, Origin},
+};
+
+pub async fn run(layered: &LayeredWordService, word: Option<&str>) -> Result<()> {
+    let base = layered.base().get_words().await?;
+    let words = layered.get_words().await?;
+
+    println!(
+        "Base: {} word(s) in {}",
+        base.len(),
+        layered.language().name
+    );
+    for (i, layer) in layered.layers().iter().enumerate() {
+        println!("  {}. {layer}", i + 1);
+    }
+
+    let base_counts = count_by_length(&base);
+    let counts = count_by_length(&words);
+    println!("\nResult: {} word(s)", words.len());
+    for (length, count) in &counts {
+        let before = base_counts.get(length).copied().unwrap_or(0);
+        println!(
+            "  {length:>3} letters: {count} ({:+})",
+            *count as isize - before as isize
+        );
+    }
+
+    if let Some(word) = word {
+        println!();
+        let (resolved, origin) = layered.resolve(word).await?;
+        match origin {
+            Origin::Base => println!("{word} comes from the base dictionary"),
+            Origin::Added(i) => println!("{word} is added by layer {}", i + 1),
+            Origin::Removed(i) => println!("{word} is removed by layer {}", i + 1),
+            Origin::Missing => println!("{word} is in no layer"),
+        }
+        if let Some(word) = resolved {
+            for definition in &word.definitions {
+                println!("  - {}", definition.summary());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn count_by_length(words: &[Word]) -> BTreeMap<usize, usize> {
+    let mut counts = BTreeMap::new();
+    for word in words {
+        *counts.entry(word.word.chars().count()).or_default() += 1;
+    }
+    counts
+}
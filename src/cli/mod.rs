@@ -0,0 +1,4 @@
+pub mod dict;
+pub mod layers;
+pub mod search;
+pub mod solve;
@@ -0,0 +1,17 @@
+use color_eyre::Result;
+use wordguessr::service::{dictionary::WordService, search::WordQuery};
+
+pub async fn run(word_service: &dyn WordService, query: &WordQuery) -> Result<()> {
+    let page = word_service.search(query).await?;
+    let pages = page.total.div_ceil(page.per_page).max(1);
+    println!(
+        "{} matching word(s), page {}/{}",
+        page.total, page.page, pages
+    );
+
+    for word in &page.words {
+        println!("  {:<20} {}", word.word, word.frequency);
+    }
+
+    Ok(())
+}
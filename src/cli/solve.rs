@@ -0,0 +1,61 @@
+use color_eyre::{Result, eyre::bail};
+use wordguessr::{
+    service::dictionary::WordService,
+    solver::{Observation, Solver},
+};
+
+/// Candidates are listed when there are at most this many left
+const MAX_LISTED_CANDIDATES: usize = 20;
+
+pub async fn run(
+    word_service: &dyn WordService,
+    word_length: usize,
+    observations: &[Observation],
+    top: usize,
+) -> Result<()> {
+    let word_length = observations
+        .first()
+        .map_or(word_length, |observation| observation.guess.len());
+    if observations
+        .iter()
+        .any(|observation| observation.guess.len() != word_length)
+    {
+        bail!("All guesses must have the same length");
+    }
+
+    // Guesses and words are compared in normalized form, like in games
+    let language = word_service.language();
+    let observations: Vec<Observation> = observations
+        .iter()
+        .cloned()
+        .map(|mut observation| {
+            observation.guess = observation
+                .guess
+                .iter()
+                .map(|&c| language.base_letter(c))
+                .collect();
+            observation
+        })
+        .collect();
+    let observations = &observations[..];
+
+    let spellings = word_service.get_spellings_by_length(word_length).await?;
+    let solver = Solver::new(&spellings);
+    let candidates = solver.candidates(observations);
+    println!("{} possible word(s) left", candidates.len());
+    if candidates.len() <= MAX_LISTED_CANDIDATES {
+        for candidate in &candidates {
+            println!("  {candidate}");
+        }
+    }
+
+    if candidates.len() > 1 {
+        println!("\nBest guesses (* could be the secret):");
+        for guess in solver.rank_guesses(observations, top) {
+            let marker = if guess.is_candidate { "*" } else { " " };
+            println!("  {marker} {} {:.3} bits", guess.word, guess.entropy);
+        }
+    }
+
+    Ok(())
+}
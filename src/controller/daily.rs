@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use serde::Deserialize;
+
+use crate::{
+    model::{date::Date, settings::GameSettings},
+    service::dictionary::Word,
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a, which unlike the hashers of the standard library gives the same result on every
+/// platform and release, so every server agrees on the word of a day
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in parts.iter().flat_map(|part| part.bytes().chain([0])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Daily puzzle chosen by an administrator
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScheduledPuzzle {
+    pub date: Date,
+    pub word: String,
+    /// Shown above the grid, e.g. "Rust week"
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Shown above the grid before the first guess
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+/// Splits a CSV line into its fields, which may be quoted to hold commas, with `""` for a quote
+fn parse_csv_record(line: &str) -> Result<Vec<String>> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("There is always a field");
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        bail!("Unterminated quote");
+    }
+    Ok(fields.into_iter().map(|f| f.trim().to_string()).collect())
+}
+
+impl ScheduledPuzzle {
+    /// Reads a JSON list of puzzles, or CSV lines of `date,word,theme,hint` where the theme and
+    /// hint are optional and a header line is skipped
+    pub fn load(path: &Path) -> Result<Vec<Self>> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Can't read schedule {}", path.display()))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            return serde_json::from_str(&text)
+                .wrap_err_with(|| format!("Invalid schedule {}", path.display()));
+        }
+
+        let mut puzzles = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let location = || format!("{}:{}", path.display(), i + 1);
+            let fields = parse_csv_record(line).wrap_err_with(location)?;
+            let [date, word, rest @ ..] = fields.as_slice() else {
+                bail!("{}: expected date,word,theme,hint", location());
+            };
+            if i == 0 && date.eq_ignore_ascii_case("date") {
+                continue;
+            }
+            let optional = |field: Option<&String>| field.filter(|f| !f.is_empty()).cloned();
+            puzzles.push(Self {
+                date: date.parse().wrap_err_with(location)?,
+                word: word.clone(),
+                theme: optional(rest.first()),
+                hint: optional(rest.get(1)),
+            });
+        }
+        Ok(puzzles)
+    }
+}
+
+/// Which word is the daily puzzle of each day
+pub struct DailySchedule {
+    /// Settings of every daily game
+    pub settings: GameSettings,
+    /// Day of the first daily puzzle
+    pub start: Date,
+    /// Days whose puzzle was chosen by an administrator, the others are picked from the words
+    scheduled: BTreeMap<Date, ScheduledPuzzle>,
+}
+
+impl DailySchedule {
+    pub fn new(settings: GameSettings, start: Date) -> Self {
+        Self {
+            settings,
+            start,
+            scheduled: BTreeMap::new(),
+        }
+    }
+
+    /// Uses the given puzzles on their days, there can only be one per day
+    pub fn with_puzzles(mut self, puzzles: Vec<ScheduledPuzzle>) -> Result<Self> {
+        for puzzle in puzzles {
+            if let Some(previous) = self.scheduled.get(&puzzle.date) {
+                bail!(
+                    "Both {} and {} are scheduled on {}",
+                    previous.word,
+                    puzzle.word,
+                    puzzle.date
+                );
+            }
+            self.scheduled.insert(puzzle.date, puzzle);
+        }
+        Ok(self)
+    }
+
+    pub fn scheduled(&self, date: Date) -> Option<&ScheduledPuzzle> {
+        self.scheduled.get(&date)
+    }
+
+    pub fn scheduled_puzzles(&self) -> impl Iterator<Item = &ScheduledPuzzle> {
+        self.scheduled.values()
+    }
+
+    /// Whether there is a puzzle for `date` that can be played on `today`
+    pub fn is_open(&self, date: Date, today: Date) -> bool {
+        self.start <= date && date <= today
+    }
+
+    /// Days with a puzzle up to `today`, most recent first
+    pub fn dates(&self, today: Date) -> impl Iterator<Item = Date> {
+        let start = self.start;
+        (0..=today.days_since(start)).map(move |days| today.add_days(-days))
+    }
+
+    /// The candidate whose hash with the date is the lowest, so that the word of a day only
+    /// changes when its word is removed or a new word beats it
+    pub fn pick<'w>(&self, date: Date, candidates: &'w [Word]) -> Option<&'w Word> {
+        let date = date.to_string();
+        candidates.iter().min_by_key(|word| {
+            (
+                fnv1a(&[&self.settings.language, &date, &word.word]),
+                &word.word,
+            )
+        })
+    }
+}
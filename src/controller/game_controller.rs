@@ -1,27 +1,619 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use rand::seq::IndexedRandom;
+use uuid::Uuid;
 
 use crate::{
+    controller::daily::DailySchedule,
     model::{
-        cell::{Cell, CellState},
+        analysis::Analysis,
+        cell::CellState,
+        date::Date,
         game_state::{GameState, GameStatus},
         grid::GridError,
+        hint::{Hint, HintKind},
+        pack::PackSummary,
+        profile::{DailyResult, PlayerProfile},
+        schedule::PreviewDay,
+        settings::{GameSettings, PlayerSettings, SettingsBounds, SettingsPanel},
+        stats::PlayerStats,
+        usage::WordUsage,
+    },
+    rules::{GameRules, RulesRegistry, classic::Classic, determine_letter_counts},
+    service::{
+        blocklist::{BlockedWordService, Blocklist},
+        dictionary::{Word, WordService},
+        language::{DictionaryRegistry, Language},
+        pack::PackRegistry,
+        profile::ProfileStore,
     },
-    service::dictionary::{Word, WordService},
+    solver::{FeedbackKind, Observation, RankedGuess, Solver, constraints::Constraints},
+};
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail},
 };
-use color_eyre::Result;
 
 pub struct GameController {
-    word_service: Arc<dyn WordService>,
+    dictionaries: DictionaryRegistry,
+    /// Words that can be secrets or shown to players, the dictionaries without blocked words
+    secrets: DictionaryRegistry,
+    /// Whether blocked words are still valid guesses
+    accept_blocked_guesses: bool,
+    rules: RulesRegistry,
+    /// Best first guess per language, word length and kind of feedback, used by the
+    /// post-game analysis
+    openers: Mutex<HashMap<(String, usize, FeedbackKind), RankedGuess>>,
+    /// Outcome of the games each word was the secret of
+    usage: Mutex<HashMap<String, WordUsage>>,
+    profiles: ProfileStore,
+    daily: Option<DailySchedule>,
+    packs: PackRegistry,
+    /// What players can choose in their settings
+    bounds: SettingsBounds,
 }
 
 impl GameController {
-    pub fn new(word_service: Arc<dyn WordService>) -> Self {
-        Self { word_service }
+    pub fn new(dictionaries: DictionaryRegistry, rules: RulesRegistry) -> Self {
+        Self {
+            secrets: dictionaries.clone(),
+            dictionaries,
+            accept_blocked_guesses: true,
+            rules,
+            openers: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+            profiles: ProfileStore::in_memory(),
+            daily: None,
+            packs: PackRegistry::empty(),
+            bounds: SettingsBounds::default(),
+        }
+    }
+
+    pub fn with_packs(mut self, packs: PackRegistry) -> Self {
+        self.packs = packs;
+        self
+    }
+
+    pub fn with_settings_bounds(mut self, bounds: SettingsBounds) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    pub fn with_daily_schedule(mut self, schedule: DailySchedule) -> Self {
+        self.daily = Some(schedule);
+        self
+    }
+
+    pub fn with_profiles(mut self, profiles: ProfileStore) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    pub fn profile(&self, player: Uuid) -> PlayerProfile {
+        self.profiles.get(player)
+    }
+
+    pub fn player_stats(&self, player: Uuid) -> PlayerStats {
+        self.profiles.get(player).stats
+    }
+
+    /// Keeps the words of `blocklist` out of the secrets, suggestions and word lists
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.accept_blocked_guesses = blocklist.accept_guesses;
+        let blocklist = Arc::new(blocklist);
+        self.secrets = DictionaryRegistry::empty();
+        for dictionary in self.dictionaries.services() {
+            self.secrets.register(Arc::new(BlockedWordService::new(
+                dictionary.clone(),
+                blocklist.clone(),
+            )));
+        }
+        self
+    }
+
+    pub fn rules(&self) -> &RulesRegistry {
+        &self.rules
+    }
+
+    pub fn dictionaries(&self) -> &DictionaryRegistry {
+        &self.dictionaries
+    }
+
+    /// The dictionaries without the blocked words
+    pub fn secrets(&self) -> &DictionaryRegistry {
+        &self.secrets
+    }
+
+    /// Drops what was computed from the previous words, after the dictionaries are reloaded
+    pub fn forget_openers(&self) {
+        self.openers.lock().expect("Openers lock poisoned").clear();
+    }
+
+    pub fn word_usage(&self, word: &str) -> WordUsage {
+        self.usage
+            .lock()
+            .expect("Usage lock poisoned")
+            .get(word)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn update_usage(&self, word: &str, update: impl FnOnce(&mut WordUsage)) {
+        let mut usage = self.usage.lock().expect("Usage lock poisoned");
+        update(usage.entry(word.to_string()).or_default());
     }
 
-    pub async fn create_new_game(&self, num_tries: usize, word_length: usize) -> Result<GameState> {
-        let secret_word = self.word_service.get_random_word(word_length).await?;
-        Ok(GameState::new(secret_word, num_tries, word_length))
+    fn rules_for(&self, mode: &str) -> Result<Arc<dyn GameRules>> {
+        self.rules
+            .get(mode)
+            .ok_or_eyre(format!("Unknown game mode {mode}"))
+    }
+
+    fn dictionary_for(&self, language: &str) -> Result<Arc<dyn WordService>> {
+        self.dictionaries
+            .get(language)
+            .ok_or_eyre(format!("Unknown language {language}"))
+    }
+
+    fn secrets_for(&self, language: &str) -> Result<Arc<dyn WordService>> {
+        self.secrets
+            .get(language)
+            .ok_or_eyre(format!("Unknown language {language}"))
+    }
+
+    /// Checks that `choice` is allowed and has secret words, with a message for the player
+    pub async fn check_player_settings(&self, choice: &PlayerSettings) -> Result<()> {
+        let bounds = &self.bounds;
+        if !bounds.word_lengths.contains(&choice.word_length) {
+            bail!(
+                "Words have {} to {} letters",
+                bounds.word_lengths.start(),
+                bounds.word_lengths.end()
+            );
+        }
+        if !bounds.tries.contains(&choice.num_tries) {
+            bail!(
+                "Games have {} to {} tries",
+                bounds.tries.start(),
+                bounds.tries.end()
+            );
+        }
+        let rules = self
+            .rules_for(&choice.mode)
+            .ok()
+            .filter(|_| bounds.allows_mode(&choice.mode))
+            .ok_or_eyre(format!("There is no {} mode", choice.mode))?;
+        let dictionary = self
+            .secrets_for(&choice.language)
+            .ok()
+            .filter(|_| bounds.allows_language(&choice.language))
+            .ok_or_eyre(format!("There is no {} dictionary", choice.language))?;
+
+        let words = dictionary.get_words_by_length(choice.word_length).await?;
+        if !words.iter().any(|word| rules.accepts_secret(word)) {
+            bail!(
+                "The {} dictionary has no words of {} letters for the {} mode",
+                dictionary.language().name,
+                choice.word_length,
+                rules.name()
+            );
+        }
+        Ok(())
+    }
+
+    /// Settings of the next random game of `player`, `defaults` until they choose or when
+    /// their choice is no longer allowed
+    pub async fn player_settings(
+        &self,
+        player: Option<Uuid>,
+        defaults: &GameSettings,
+    ) -> GameSettings {
+        let Some(choice) = player.and_then(|player| self.profiles.get(player).settings) else {
+            return defaults.clone();
+        };
+        match self.check_player_settings(&choice).await {
+            Ok(()) => choice.apply(defaults),
+            Err(_) => defaults.clone(),
+        }
+    }
+
+    /// Remembers the choice of `player` for their next games, after checking it
+    pub async fn choose_settings(
+        &self,
+        player: Option<Uuid>,
+        choice: PlayerSettings,
+        defaults: &GameSettings,
+    ) -> Result<GameSettings> {
+        self.check_player_settings(&choice).await?;
+        let settings = choice.apply(defaults);
+        if let Some(player) = player {
+            self.profiles
+                .update(player, |profile| profile.settings = Some(choice))?;
+        }
+        Ok(settings)
+    }
+
+    /// Settings panel showing `choice`, with the modes and languages players can choose
+    pub fn settings_panel(&self, choice: PlayerSettings, error: Option<String>) -> SettingsPanel {
+        SettingsPanel {
+            choice,
+            bounds: self.bounds.clone(),
+            modes: self
+                .rules
+                .modes()
+                .filter(|rules| self.bounds.allows_mode(rules.id()))
+                .map(|rules| (rules.id().to_string(), rules.name().to_string()))
+                .collect(),
+            languages: self
+                .secrets
+                .languages()
+                .filter(|language| self.bounds.allows_language(&language.id))
+                .map(|language| (language.id.clone(), language.name.clone()))
+                .collect(),
+            error,
+        }
+    }
+
+    /// Starts a game for `player`, whose secret is one they haven't played before as long as
+    /// there are such words left
+    pub async fn create_new_game(
+        &self,
+        settings: &GameSettings,
+        player: Option<Uuid>,
+    ) -> Result<GameState> {
+        let rules = self.rules_for(&settings.mode)?;
+        let dictionary = self.secrets_for(&settings.language)?;
+        let profile = player.map(|player| self.profiles.get(player));
+        let unseen = |word: &Word| {
+            profile.as_ref().is_none_or(|profile| {
+                !profile.has_seen(&settings.language, settings.word_length, &word.word)
+            })
+        };
+
+        let secret_word = match dictionary
+            .get_random_word_where(settings.word_length, settings.difficulty, &|word| {
+                rules.accepts_secret(word) && unseen(word)
+            })
+            .await
+        {
+            Ok(word) => word,
+            Err(_) if profile.is_some() => {
+                // The player has seen every word, start their history over
+                let word = dictionary
+                    .get_random_word_where(settings.word_length, settings.difficulty, &|word| {
+                        rules.accepts_secret(word)
+                    })
+                    .await?;
+                if let Some(player) = player {
+                    self.profiles.update(player, |profile| {
+                        profile.forget_seen(&settings.language, settings.word_length)
+                    })?;
+                }
+                word
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(player) = player {
+            self.profiles.update(player, |profile| {
+                profile.mark_seen(&settings.language, settings.word_length, &secret_word.word)
+            })?;
+        }
+
+        self.update_usage(&secret_word.word, |usage| usage.picked += 1);
+
+        let mut game_state =
+            Self::game_state(secret_word, settings, rules.as_ref(), dictionary.language());
+        game_state.player = player;
+        Ok(game_state)
+    }
+
+    fn game_state(
+        secret_word: Word,
+        settings: &GameSettings,
+        rules: &dyn GameRules,
+        language: &Language,
+    ) -> GameState {
+        let mut settings = settings.clone();
+        if !rules.allows_hints() {
+            settings.max_hints = 0;
+        }
+
+        let mut game_state = GameState::new(secret_word, settings, language.clone());
+        game_state.clue = rules.clue(&game_state.secret_word);
+        game_state
+    }
+
+    pub fn daily_schedule(&self) -> Option<&DailySchedule> {
+        self.daily.as_ref()
+    }
+
+    fn open_daily_schedule(&self, date: Date) -> Result<&DailySchedule> {
+        let schedule = self.daily.as_ref().ok_or_eyre("There is no daily puzzle")?;
+        if !schedule.is_open(date, Date::today()) {
+            bail!("There is no daily puzzle for {date}");
+        }
+        Ok(schedule)
+    }
+
+    /// Secret word of the daily puzzle of `date`, the same for every player
+    pub async fn daily_word(&self, date: Date) -> Result<Word> {
+        let schedule = self.open_daily_schedule(date)?;
+        self.pick_daily_word(schedule, date).await
+    }
+
+    /// The scheduled word of `date` if there is one, otherwise a word picked from the dictionary
+    async fn pick_daily_word(&self, schedule: &DailySchedule, date: Date) -> Result<Word> {
+        let settings = &schedule.settings;
+        let secrets = self.secrets_for(&settings.language)?;
+        if let Some(puzzle) = schedule.scheduled(date) {
+            match secrets.get_word(&puzzle.word).await? {
+                Some(word) => return Ok(word),
+                None => eprintln!(
+                    "Scheduled word {} of {date} isn't available anymore, picking another",
+                    puzzle.word
+                ),
+            }
+        }
+
+        let rules = self.rules_for(&settings.mode)?;
+        let mut candidates: Vec<Word> = secrets
+            .get_words_by_length(settings.word_length)
+            .await?
+            .into_iter()
+            .filter(|word| rules.accepts_secret(word))
+            .collect();
+        // Same selection as for random games, without weights
+        if candidates.iter().any(|word| word.commonness.is_some()) {
+            candidates.retain(|word| settings.difficulty.accepts(word));
+        }
+
+        schedule
+            .pick(date, &candidates)
+            .cloned()
+            .ok_or_eyre(format!("No word available for the daily puzzle of {date}"))
+    }
+
+    /// Checks that the scheduled words can be secret words, reporting every problem at once
+    pub async fn validate_daily_schedule(&self) -> Result<()> {
+        let Some(schedule) = &self.daily else {
+            return Ok(());
+        };
+        let rules = self.rules_for(&schedule.settings.mode)?;
+        let secrets = self.secrets_for(&schedule.settings.language)?;
+
+        let mut problems = Vec::new();
+        for puzzle in schedule.scheduled_puzzles() {
+            let (date, word) = (puzzle.date, &puzzle.word);
+            if date < schedule.start {
+                problems.push(format!("{word} on {date} is before the first daily puzzle"));
+            }
+            match secrets.get_word(word).await? {
+                None => problems.push(format!(
+                    "{word} on {date} isn't in the dictionary or is blocked"
+                )),
+                Some(secret) if !rules.accepts_secret(&secret) => problems.push(format!(
+                    "{word} on {date} can't be a secret word in {} mode",
+                    rules.name()
+                )),
+                Some(_) => {}
+            }
+        }
+
+        if !problems.is_empty() {
+            bail!("Invalid daily schedule:\n  {}", problems.join("\n  "));
+        }
+        Ok(())
+    }
+
+    /// Puzzles of the `days` days from `from`, including days that aren't open yet
+    pub async fn daily_preview(&self, from: Date, days: usize) -> Result<Vec<PreviewDay>> {
+        let schedule = self.daily.as_ref().ok_or_eyre("There is no daily puzzle")?;
+        let mut preview = Vec::new();
+        for date in (0..days as i64).map(|days| from.add_days(days)) {
+            let scheduled = schedule.scheduled(date);
+            preview.push(PreviewDay {
+                date,
+                word: self.pick_daily_word(schedule, date).await?.word,
+                scheduled: scheduled.is_some(),
+                theme: scheduled.and_then(|puzzle| puzzle.theme.clone()),
+                hint: scheduled.and_then(|puzzle| puzzle.hint.clone()),
+            });
+        }
+        Ok(preview)
+    }
+
+    /// Daily puzzle of `date` as it looks before the first guess
+    pub async fn daily_game(&self, date: Date) -> Result<GameState> {
+        let schedule = self.open_daily_schedule(date)?;
+        let secret_word = self.pick_daily_word(schedule, date).await?;
+        let rules = self.rules_for(&schedule.settings.mode)?;
+        let dictionary = self.secrets_for(&schedule.settings.language)?;
+
+        // Scheduled words may be longer or shorter than the picked ones
+        let settings = GameSettings {
+            word_length: secret_word.word.chars().count(),
+            ..schedule.settings.clone()
+        };
+        let mut game_state = Self::game_state(
+            secret_word,
+            &settings,
+            rules.as_ref(),
+            dictionary.language(),
+        );
+        game_state.daily = Some(date);
+        if let Some(puzzle) = schedule.scheduled(date) {
+            game_state.theme = puzzle.theme.clone();
+            game_state.daily_hint = puzzle.hint.clone();
+        }
+        Ok(game_state)
+    }
+
+    /// Starts the daily puzzle of `date`, which can be replayed from the archive
+    pub async fn create_daily_game(&self, date: Date, player: Option<Uuid>) -> Result<GameState> {
+        let mut game_state = self.daily_game(date).await?;
+        self.update_usage(&game_state.secret_word.word, |usage| usage.picked += 1);
+        game_state.player = player;
+        Ok(game_state)
+    }
+
+    /// Puzzle packs with the progress of `player` in each
+    pub fn pack_summaries(&self, player: Option<Uuid>) -> Vec<PackSummary> {
+        let profile = player.map(|player| self.profiles.get(player));
+        self.packs
+            .packs()
+            .map(|pack| PackSummary {
+                id: pack.id.clone(),
+                name: pack.name.clone(),
+                theme: pack.theme.clone(),
+                solved: profile
+                    .as_ref()
+                    .and_then(|profile| profile.solved_in_pack(&pack.id))
+                    .map_or(0, |solved| {
+                        pack.words
+                            .iter()
+                            .filter(|word| solved.contains(*word))
+                            .count()
+                    }),
+                total: pack.words.len(),
+            })
+            .collect()
+    }
+
+    /// Game on a word of the pack `id` as it looks before the first guess, with the settings of
+    /// the pack over `settings`. Words `player` hasn't found yet come first.
+    pub async fn pack_game(
+        &self,
+        id: &str,
+        settings: &GameSettings,
+        player: Option<Uuid>,
+    ) -> Result<GameState> {
+        let pack = self
+            .packs
+            .get(id)
+            .ok_or_eyre(format!("Unknown puzzle pack {id}"))?;
+        let mut mode = pack.mode.clone().unwrap_or_else(|| settings.mode.clone());
+        let mut rules = self.rules_for(&mode)?;
+        // The secret must be a word of the pack, modes swapping it are played as classic
+        if !rules.keeps_secret() {
+            mode = Classic.id().to_string();
+            rules = self.rules_for(&mode)?;
+        }
+        let profile = player.map(|player| self.profiles.get(player));
+        let solved = profile
+            .as_ref()
+            .and_then(|profile| profile.solved_in_pack(id));
+        // Looked up on every game, as the dictionary may have been reloaded since the pack was,
+        // removing or blocking some of its words. Packs without a mode are played in the
+        // player's, which may not accept every word.
+        let secrets = self.secrets_for(&pack.language)?;
+        let mut playable: Vec<Word> = Vec::new();
+        for word in &pack.words {
+            if let Some(word) = secrets.get_word(word).await?
+                && rules.accepts_secret(&word)
+            {
+                playable.push(word);
+            }
+        }
+        let unsolved: Vec<&Word> = playable
+            .iter()
+            .filter(|word| solved.is_none_or(|solved| !solved.contains(&word.word)))
+            .collect();
+        let candidates: Vec<&Word> = if unsolved.is_empty() {
+            playable.iter().collect()
+        } else {
+            unsolved
+        };
+        let secret_word = (*candidates.choose(&mut rand::rng()).ok_or_eyre(format!(
+            "Pack {id} has no words left for the {} mode",
+            rules.name()
+        ))?)
+        .clone();
+
+        let settings = GameSettings {
+            word_length: secret_word.word.chars().count(),
+            num_tries: pack.tries.unwrap_or(settings.num_tries),
+            mode,
+            language: pack.language.clone(),
+            ..settings.clone()
+        };
+        let dictionary = self.dictionary_for(&pack.language)?;
+
+        let mut game_state = Self::game_state(
+            secret_word,
+            &settings,
+            rules.as_ref(),
+            dictionary.language(),
+        );
+        game_state.pack = Some(pack.id.clone());
+        Ok(game_state)
+    }
+
+    /// Starts a game on a word of the pack `id`
+    pub async fn create_pack_game(
+        &self,
+        id: &str,
+        settings: &GameSettings,
+        player: Option<Uuid>,
+    ) -> Result<GameState> {
+        let mut game_state = self.pack_game(id, settings, player).await?;
+        self.update_usage(&game_state.secret_word.word, |usage| usage.picked += 1);
+        game_state.player = player;
+        Ok(game_state)
+    }
+
+    /// Records the outcome of a game that just ended
+    fn finish_game(&self, game_state: &GameState) {
+        let won = game_state.status == GameStatus::Won;
+        self.update_usage(&game_state.secret_word.word, |usage| {
+            if won {
+                usage.won += 1;
+            } else {
+                usage.lost += 1;
+            }
+        });
+
+        if let (Some(pack), Some(player), true) = (&game_state.pack, game_state.player, won)
+            && let Err(e) = self.profiles.update(player, |profile| {
+                profile.mark_solved(pack, &game_state.secret_word.word)
+            })
+        {
+            eprintln!("Error saving the pack progress: {e:?}");
+        }
+
+        // Before the daily result, which tells replays of a daily puzzle apart. Past puzzles
+        // from the archive don't count, so they can be played without breaking a streak.
+        if let Some(player) = game_state.player {
+            let row = game_state.grid.current_row + 1;
+            let counts = |profile: &PlayerProfile| match game_state.daily {
+                Some(date) => date == Date::today() && profile.daily_result(date).is_none(),
+                None => true,
+            };
+            if let Err(e) = self.profiles.update(player, |profile| {
+                if counts(profile) {
+                    profile.stats.record(won, row);
+                }
+            }) {
+                eprintln!("Error saving the player statistics: {e:?}");
+            }
+        }
+
+        if let (Some(date), Some(player)) = (game_state.daily, game_state.player) {
+            let result = DailyResult {
+                won,
+                tries: game_state.grid.current_row + 1,
+                on_the_day: date == Date::today(),
+            };
+            if let Err(e) = self
+                .profiles
+                .update(player, |profile| profile.record_daily(date, result))
+            {
+                eprintln!("Error saving the daily result: {e:?}");
+            }
+        }
     }
 
     pub async fn process_guess(
@@ -33,70 +625,199 @@ impl GameController {
             return Ok(GuessResult::GameAlreadyOver);
         }
 
-        let guess_word: String = guess
+        let rules = self.rules_for(&game_state.settings.mode)?;
+        let secrets = self.secrets_for(&game_state.settings.language)?;
+        let dictionary = if self.accept_blocked_guesses {
+            self.dictionary_for(&game_state.settings.language)?
+        } else {
+            secrets.clone()
+        };
+
+        // Compared in normalized form, so È can be typed as E when the language allows it
+        let guess: Vec<char> = guess
             .iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<String>();
+            .map(|&c| game_state.language.base_letter(c))
+            .collect();
+        let guess_word: String = guess.iter().collect();
 
         // Validate if the word exists in the dictionary
-        if !self.word_service.validate_word(&guess_word).await?
-            || guess_word.len() != game_state.secret_word.word.len()
+        if !dictionary.validate_word(&guess_word).await?
+            || guess.len() != game_state.secret_word.word.chars().count()
+            || !rules.validate_guess(game_state, &guess)
         {
             return Ok(GuessResult::InvalidWord);
         }
 
         // Update grid with guess
+        let states = rules
+            .score_guess(game_state, &guess, secrets.as_ref())
+            .await?;
         let current_row = game_state.grid.current_row;
-        game_state.grid.rows[current_row].cells = self
-            .determine_cell_states(&guess, &game_state.secret_word)
-            .into_iter()
-            .zip(guess.iter())
-            .map(|(state, letter)| Cell::new(Some(*letter), false).with_state(state))
-            .collect();
+        rules.apply_feedback(&mut game_state.grid.rows[current_row], &guess, &states);
+
+        if game_state.settings.letter_counts && rules.reveals_letter_counts() {
+            let secret: Vec<char> = game_state
+                .language
+                .normalize(&game_state.secret_word.word)
+                .chars()
+                .collect();
+            let counts = determine_letter_counts(&guess, &secret, &states);
+            for (cell, occurrences) in game_state.grid.rows[current_row]
+                .cells
+                .iter_mut()
+                .zip(counts)
+            {
+                cell.occurrences = occurrences;
+            }
+        }
 
         // Check win condition
-        if guess_word == game_state.secret_word.word {
-            game_state.grid.rows[game_state.grid.current_row].set_disabled(true);
+        if rules.is_win(&states) {
+            game_state.grid.rows[current_row].set_disabled(true);
             game_state.status = GameStatus::Won;
+            self.finish_game(game_state);
             return Ok(GuessResult::Won);
         }
 
         // Try to advance to next row
         match game_state.grid.advance_row() {
-            Ok(_) => Ok(GuessResult::Continue),
+            Ok(_) => {
+                game_state.apply_hints();
+                Ok(GuessResult::Continue)
+            }
             Err(GridError::NoMoreRows) => {
                 game_state.grid.rows[game_state.grid.current_row].set_disabled(true);
                 game_state.status = GameStatus::Lost;
+                self.finish_game(game_state);
                 Ok(GuessResult::Lost)
             }
         }
     }
 
-    fn determine_cell_states(&self, guess: &[char], secret: &Word) -> Vec<CellState> {
-        let mut secret_chars: Vec<char> = secret.word.chars().collect();
-        let mut states = vec![CellState::Absent; guess.len()];
+    /// Words of the dictionary that are still consistent with the feedback of the grid
+    pub async fn remaining_words(&self, game_state: &GameState) -> Result<Vec<String>> {
+        let constraints = Constraints::new(&Observation::from_grid(&game_state.grid));
+        Ok(self
+            .secrets_for(&game_state.settings.language)?
+            .get_spellings_by_length(game_state.settings.word_length)
+            .await?
+            .iter()
+            .filter(|spelling| constraints.matches(&spelling.letters))
+            .map(|spelling| spelling.word.clone())
+            .collect())
+    }
+
+    /// Compares each guess of a finished game with the best play. This is CPU heavy, so the
+    /// solver runs on the blocking thread pool.
+    pub async fn analyze_game(&self, game_state: &GameState) -> Result<Analysis> {
+        let observations = Observation::from_grid(&game_state.grid);
+        // Counts-only games are judged on the counts their players see
+        let kind = FeedbackKind::of(&observations);
+        let key = (
+            game_state.settings.language.clone(),
+            game_state.settings.word_length,
+            kind,
+        );
+        let dictionary = self.secrets_for(&game_state.settings.language)?;
+        let spellings = dictionary.get_spellings_by_length(key.1).await?;
+        let solver = Solver::new(&spellings);
+        let opener = self
+            .openers
+            .lock()
+            .expect("Openers lock poisoned")
+            .get(&key)
+            .cloned();
 
-        // Mark correct positions first
-        for (i, (g, s)) in guess.iter().zip(secret_chars.iter_mut()).enumerate() {
-            if g == s {
-                states[i] = CellState::Correct;
-                *s = '\0';
-            }
+        let (analysis, opener) = tokio::task::spawn_blocking(move || {
+            let opener =
+                opener.or_else(|| solver.rank_guesses_for(&[], kind, 1).into_iter().next());
+            (solver.analyze(&observations, kind, opener.clone()), opener)
+        })
+        .await?;
+
+        if let Some(opener) = opener {
+            self.openers
+                .lock()
+                .expect("Openers lock poisoned")
+                .entry(key)
+                .or_insert(opener);
         }
 
-        // Mark present letters
-        for (i, g) in guess.iter().enumerate() {
-            if states[i] == CellState::Absent
-                && let Some(j) = secret_chars.iter().position(|s| s == g)
-            {
-                states[i] = CellState::Present;
-                secret_chars[j] = '\0';
+        Ok(analysis)
+    }
+
+    pub fn request_hint(&self, game_state: &mut GameState, kind: HintKind) -> HintResult {
+        if game_state.is_game_over() || game_state.hints_left() == 0 {
+            return HintResult::Unavailable;
+        }
+
+        let hint = match kind {
+            HintKind::Letter => Self::letter_hint(game_state),
+            HintKind::Definition => Self::definition_hint(game_state),
+        };
+
+        match hint {
+            Some(hint) => {
+                game_state.hints.push(hint);
+                game_state.apply_hints();
+                HintResult::Revealed
             }
+            None => HintResult::Unavailable,
         }
-        states
+    }
+
+    /// Picks a position the player hasn't found nor been given yet
+    fn letter_hint(game_state: &GameState) -> Option<Hint> {
+        // As typed, so that the hinted letter is kept when the player types over it
+        let secret: Vec<char> = game_state
+            .language
+            .normalize(&game_state.secret_word.word)
+            .chars()
+            .collect();
+        let hidden: Vec<usize> = (0..secret.len())
+            .filter(|&i| {
+                let found = game_state
+                    .grid
+                    .scored_rows()
+                    .any(|row| row.cells[i].state == CellState::Correct);
+                let hinted = game_state
+                    .hints
+                    .iter()
+                    .any(|hint| matches!(hint, Hint::Letter { position, .. } if *position == i));
+                !found && !hinted
+            })
+            .collect();
+
+        let position = *hidden.choose(&mut rand::rng())?;
+        Some(Hint::Letter {
+            position,
+            letter: secret[position],
+        })
+    }
+
+    /// Picks the first definition that hasn't been revealed yet
+    fn definition_hint(game_state: &GameState) -> Option<Hint> {
+        let secret = &game_state.secret_word;
+        secret
+            .definitions
+            .iter()
+            .map(|definition| match &definition.term {
+                Some(term) => secret.redact(&format!("{term}: {}", definition.sense)),
+                None => secret.redact(&definition.sense),
+            })
+            .filter(|clue| !clue.is_empty())
+            .filter(|clue| game_state.clue.as_ref() != Some(clue))
+            .find(|clue| !game_state.hints.contains(&Hint::Definition(clue.clone())))
+            .map(Hint::Definition)
     }
 }
 
+#[derive(Debug)]
+pub enum HintResult {
+    Revealed,
+    Unavailable,
+}
+
 #[derive(Debug)]
 pub enum GuessResult {
     Won,
@@ -105,3 +826,137 @@ pub enum GuessResult {
     InvalidWord,
     GameAlreadyOver,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{
+        commonness::Difficulty, dictionary::DictionaryService, language::DiacriticPolicy,
+        pack::PuzzlePack,
+    };
+
+    fn finished_game(player: Uuid, daily: Option<Date>, status: GameStatus) -> GameState {
+        let secret_word = Word {
+            word: "CRATE".to_string(),
+            meanings: Vec::new(),
+            frequency: 0,
+            commonness: None,
+            definitions: Vec::new(),
+        };
+        let settings = GameSettings {
+            word_length: 5,
+            num_tries: 6,
+            mode: "classic".to_string(),
+            language: "en".to_string(),
+            max_hints: 0,
+            letter_counts: false,
+            reveal_remaining: false,
+            difficulty: Difficulty::default(),
+        };
+        let mut game_state = GameState::new(secret_word, settings, Language::english());
+        game_state.player = Some(player);
+        game_state.daily = daily;
+        game_state.status = status;
+        game_state
+    }
+
+    #[test]
+    fn letter_hints_are_typed_letters() {
+        let language = Language {
+            diacritics: DiacriticPolicy::Ignored,
+            alphabet: "ABCDEFGHILMNOPQRSTUVZÀÈÉÌÒÙ".chars().collect(),
+            ..Language::english()
+        };
+        let mut game_state = finished_game(Uuid::new_v4(), None, GameStatus::Playing);
+        game_state.secret_word.word = "CAFFÈ".to_string();
+        game_state.language = language;
+
+        while let Some(hint) = GameController::letter_hint(&game_state) {
+            game_state.hints.push(hint);
+        }
+        let mut letters: Vec<(usize, char)> = game_state
+            .hints
+            .iter()
+            .filter_map(|hint| match hint {
+                Hint::Letter { position, letter } => Some((*position, *letter)),
+                _ => None,
+            })
+            .collect();
+        letters.sort();
+        let hinted: String = letters.into_iter().map(|(_, letter)| letter).collect();
+        assert_eq!(hinted, "CAFFE");
+    }
+
+    #[test]
+    fn archive_days_leave_the_stats_alone() {
+        let controller = GameController::new(DictionaryRegistry::empty(), RulesRegistry::empty());
+        let player = Uuid::new_v4();
+
+        controller.finish_game(&finished_game(player, None, GameStatus::Won));
+        let before = controller.player_stats(player);
+        assert_eq!(before.current_streak, 1);
+
+        let past_day = Date::today().add_days(-3);
+        controller.finish_game(&finished_game(player, Some(past_day), GameStatus::Lost));
+        let after = controller.player_stats(player);
+        assert_eq!(after.played, before.played);
+        assert_eq!(after.current_streak, before.current_streak);
+        assert_eq!(after.distribution, before.distribution);
+        assert!(controller.profile(player).daily_result(past_day).is_some());
+    }
+
+    #[test]
+    fn daily_puzzles_count_once_on_their_day() {
+        let controller = GameController::new(DictionaryRegistry::empty(), RulesRegistry::empty());
+        let player = Uuid::new_v4();
+        let today = Some(Date::today());
+
+        controller.finish_game(&finished_game(player, today, GameStatus::Won));
+        controller.finish_game(&finished_game(player, today, GameStatus::Lost));
+        let stats = controller.player_stats(player);
+        assert_eq!(stats.played, 1);
+        assert_eq!(stats.current_streak, 1);
+    }
+
+    /// Packs whose words were removed from the dictionary since they were loaded
+    #[tokio::test]
+    async fn pack_games_skip_removed_words() {
+        let dictionary = DictionaryService::load(
+            Language::english(),
+            r#"{"words": {"crate": "A box", "otter": "An animal"}}"#,
+        )
+        .unwrap();
+        let mut dictionaries = DictionaryRegistry::empty();
+        dictionaries.register(Arc::new(dictionary));
+        let pack = |id: &str, words: &[&str]| PuzzlePack {
+            id: id.to_string(),
+            name: id.to_string(),
+            theme: None,
+            language: "en".to_string(),
+            tries: None,
+            mode: None,
+            words: words.iter().map(|word| word.to_string()).collect(),
+        };
+        let mut packs = PackRegistry::empty();
+        packs.register(Arc::new(pack("boxes", &["CRATE", "GHOST"])));
+        packs.register(Arc::new(pack("ghosts", &["GHOST"])));
+        let controller =
+            GameController::new(dictionaries, RulesRegistry::default()).with_packs(packs);
+        let settings = finished_game(Uuid::new_v4(), None, GameStatus::Playing).settings;
+
+        for _ in 0..10 {
+            let game_state = controller
+                .pack_game("boxes", &settings, None)
+                .await
+                .unwrap();
+            assert_eq!(game_state.secret_word.word, "CRATE");
+            assert_eq!(game_state.secret_word.meanings, ["A box"]);
+        }
+        assert!(
+            controller
+                .pack_game("ghosts", &settings, None)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1 +1,2 @@
+pub mod daily;
 pub mod game_controller;
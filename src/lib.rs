@@ -0,0 +1,6 @@
+pub mod controller;
+pub mod model;
+pub mod rules;
+pub mod service;
+pub mod solver;
+pub mod view;
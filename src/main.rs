@@ -1,38 +1,88 @@
 use axum::{
-    Router,
+    Json, Router,
     extract::{
-        State,
+        Path, Query, State,
         ws::{Message, WebSocket, WebSocketUpgrade},
     },
-    response::{Html, IntoResponse},
-    routing::get,
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{SET_COOKIE, WWW_AUTHENTICATE},
+    },
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+};
+use axum_extra::TypedHeader;
+use clap::{Parser, Subcommand};
+use headers::{
+    Authorization, Cookie, HeaderMapExt,
+    authorization::{Basic, Bearer},
 };
-use clap::Parser;
 use maud::{Markup, Render};
-use serde::Deserialize;
-use tokio::sync::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{
+    RwLock,
+    mpsc::{self, UnboundedSender},
+};
 use uuid::Uuid;
 
-use std::{collections::HashMap, net::SocketAddr};
-use std::{ops::ControlFlow, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+use std::{ops::ControlFlow, sync::Arc, time::Duration};
 
+use async_trait::async_trait;
 use futures_util::stream::StreamExt;
 
-use color_eyre::Result;
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail, eyre},
+};
 
-use crate::{
-    controller::game_controller::GameController,
-    model::game_state::GameState,
-    service::dictionary::{DictionaryService, WordService},
+use wordguessr::{
+    controller::{
+        daily::{DailySchedule, ScheduledPuzzle},
+        game_controller::{GameController, GuessResult},
+    },
+    model::{
+        archive::{ArchiveDay, DailyArchive},
+        browser::{DictionaryBrowser, WordDetail},
+        date::Date,
+        game_state::GameState,
+        hint::HintKind,
+        pack::PackPicker,
+        schedule::SchedulePreview,
+        settings::{GameSettings, PlayerSettings, SettingsBounds},
+        stats::StatsDialog,
+    },
+    rules::RulesRegistry,
+    service::{
+        blocklist::Blocklist,
+        commonness::{Difficulty, FrequencyList},
+        dictionary::{DictionaryService, WordService},
+        fallback::FallbackWordService,
+        language::DictionaryRegistry,
+        layered::LayeredWordService,
+        pack::PackRegistry,
+        profile::ProfileStore,
+        reload::{DictionaryLoader, DictionaryReloader},
+        remote::RemoteWordService,
+        search::{WordPage, WordQuery},
+    },
+    solver::Observation,
     view::layout::Layout,
 };
 
-mod controller;
-mod model;
-mod service;
-mod view;
+mod cli;
 
-#[derive(Parser)]
+/// How often the dictionary files are checked for changes
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Days shown by the schedule preview unless asked otherwise
+const DEFAULT_PREVIEW_DAYS: usize = 14;
+const MAX_PREVIEW_DAYS: usize = 366;
+/// Cookie identifying the player across visits
+const PLAYER_COOKIE: &str = "wordguessr_player";
+/// Browsers forget the player after a year without visits
+const PLAYER_COOKIE_MAX_AGE: u64 = 365 * 24 * 60 * 60;
+
+#[derive(Parser, Clone)]
 #[command(version, about, long_about = None)]
 struct Args {
     /// Word length
@@ -41,6 +91,232 @@ struct Args {
     /// Number of tries
     #[clap(short, long, default_value_t = 6)]
     num_tries: usize,
+    /// Game mode (classic, hard, adversarial, counts-only, definition)
+    #[clap(short, long, default_value = "classic")]
+    mode: String,
+    /// Number of hints available in each game
+    #[clap(long, default_value_t = 2)]
+    max_hints: usize,
+    /// Show how many times a confirmed letter occurs in the secret word
+    #[clap(long)]
+    letter_counts: bool,
+    /// List the words that were still possible once the game is over
+    #[clap(long)]
+    reveal_remaining: bool,
+    /// How common the secret words are, needs a frequency list for easy and normal
+    #[clap(short, long, value_enum, default_value_t = Difficulty::Normal)]
+    difficulty: Difficulty,
+    /// Word frequency list of the built-in English dictionary (one word per line, most common
+    /// first, or word,count CSV)
+    #[clap(short, long)]
+    frequency_list: Option<PathBuf>,
+    /// Language of the secret words, "en" or the id of a language pack
+    #[clap(short, long, default_value = "en")]
+    language: String,
+    /// Directory with one language pack per subdirectory
+    #[clap(long)]
+    language_dir: Option<PathBuf>,
+    /// HTTP endpoint serving the words of the selected language, the local dictionary is used
+    /// when it is down
+    #[clap(long)]
+    remote_dictionary: Option<String>,
+    /// Directory where the word lists of the remote dictionary are cached
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+    /// JSON file listing the word lists stacked on the dictionary of the selected language
+    #[clap(long)]
+    layers: Option<PathBuf>,
+    /// Token expected as `Authorization: Bearer <TOKEN>` by the admin endpoints, which are
+    /// disabled without it
+    #[clap(long)]
+    admin_token: Option<String>,
+    /// JSON file listing the words, by category, that must not be secret words
+    #[clap(long)]
+    blocklist: Option<PathBuf>,
+    /// Directory where player profiles are saved, they are kept in memory only without it
+    #[clap(long)]
+    data_dir: Option<PathBuf>,
+    /// Day of the first daily puzzle (YYYY-MM-DD), daily puzzles use the settings above
+    #[clap(long, default_value = "2025-01-01")]
+    daily_start: Date,
+    /// Words of some daily puzzles, as CSV lines of date,word,theme,hint or a JSON list of
+    /// {"date", "word", "theme", "hint"} objects
+    #[clap(long)]
+    daily_schedule: Option<PathBuf>,
+    /// Directory of puzzle packs, JSON files such as
+    /// {"name": "Animals", "theme": "...", "tries": 8, "words": ["horse", "otter"]}
+    #[clap(long)]
+    pack_dir: Option<PathBuf>,
+    /// Shortest word length players can choose in their settings
+    #[clap(long, default_value_t = 4)]
+    min_word_length: usize,
+    /// Longest word length players can choose in their settings
+    #[clap(long, default_value_t = 8)]
+    max_word_length: usize,
+    /// Fewest tries players can choose in their settings
+    #[clap(long, default_value_t = 3)]
+    min_tries: usize,
+    /// Most tries players can choose in their settings
+    #[clap(long, default_value_t = 10)]
+    max_tries: usize,
+    /// Comma-separated game modes players can choose, all of them when not given
+    #[clap(long, value_delimiter = ',')]
+    player_modes: Vec<String>,
+    /// Comma-separated languages players can choose, all of them when not given
+    #[clap(long, value_delimiter = ',')]
+    player_languages: Vec<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone)]
+enum Command {
+    /// Suggest the best next guesses for the feedback received so far
+    Solve {
+        /// Scored guesses as GUESS=FEEDBACK, with g (correct), y (present) and . (absent)
+        /// letters, e.g. crane=..y.g, or GUESS=C/P in counts-only games
+        guesses: Vec<Observation>,
+        /// Number of suggestions to show
+        #[clap(short, long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Find dictionary words matching a pattern and letter constraints
+    Search(WordQuery),
+    /// Report dictionary statistics and problem entries, failing if a word length has no words
+    Dict {
+        /// Other word lengths that must have words, besides the configured one
+        #[clap(short, long)]
+        lengths: Vec<usize>,
+        /// Definitions longer than this are reported
+        #[clap(long, default_value_t = 1000)]
+        max_definition_length: usize,
+    },
+    /// Show what each layer of `--layers` changes and where a word comes from
+    Layers {
+        /// Word to trace through the layers
+        word: Option<String>,
+    },
+}
+
+impl From<&Args> for GameSettings {
+    fn from(args: &Args) -> Self {
+        Self {
+            word_length: args.word_length,
+            num_tries: args.num_tries,
+            mode: args.mode.clone(),
+            language: args.language.clone(),
+            max_hints: args.max_hints,
+            letter_counts: args.letter_counts,
+            reveal_remaining: args.reveal_remaining,
+            difficulty: args.difficulty,
+        }
+    }
+}
+
+impl From<&Args> for SettingsBounds {
+    fn from(args: &Args) -> Self {
+        Self {
+            word_lengths: args.min_word_length..=args.max_word_length,
+            tries: args.min_tries..=args.max_tries,
+            modes: args.player_modes.clone(),
+            languages: args.player_languages.clone(),
+        }
+    }
+}
+
+/// What the game socket plays, a random word when empty
+#[derive(Deserialize, Debug)]
+struct SocketParams {
+    /// Day of the daily puzzle to play
+    daily: Option<Date>,
+    /// Puzzle pack to take the word from
+    pack: Option<String>,
+    #[serde(flatten)]
+    game: GameParams,
+}
+
+/// Settings of a game linked to, e.g. `/?length=7&tries=8&mode=hard&lang=it`, the player's
+/// own settings fill in the missing ones
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct GameParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    length: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tries: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+}
+
+impl GameParams {
+    fn is_empty(&self) -> bool {
+        self.length.is_none() && self.tries.is_none() && self.mode.is_none() && self.lang.is_none()
+    }
+
+    /// `choice` with the values given in the URL
+    fn apply(&self, choice: PlayerSettings) -> Result<PlayerSettings> {
+        Ok(PlayerSettings {
+            word_length: match &self.length {
+                Some(length) => parse_number(length, "word length")?,
+                None => choice.word_length,
+            },
+            num_tries: match &self.tries {
+                Some(tries) => parse_number(tries, "number of tries")?,
+                None => choice.num_tries,
+            },
+            mode: self.mode.clone().unwrap_or(choice.mode),
+            language: self.lang.clone().unwrap_or(choice.language),
+        })
+    }
+}
+
+fn parse_number(value: &str, name: &str) -> Result<usize> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| eyre!("The {name} must be a number, not \"{value}\""))
+}
+
+fn default_preview_days() -> usize {
+    DEFAULT_PREVIEW_DAYS
+}
+
+#[derive(Deserialize, Debug)]
+struct PreviewParams {
+    #[serde(default = "default_preview_days")]
+    days: usize,
+}
+
+#[derive(Deserialize, Debug)]
+struct WordParams {
+    /// Language of the dictionary to look the word up in, the default one if missing
+    lang: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct HintRequest {
+    hint: HintKind,
+}
+
+/// Choice sent by the settings panel, whose form values are strings
+#[derive(Deserialize, Debug)]
+struct SettingsForm {
+    word_length: String,
+    num_tries: String,
+    mode: String,
+    language: String,
+}
+
+impl SettingsForm {
+    fn parse(self) -> Result<PlayerSettings> {
+        Ok(PlayerSettings {
+            word_length: parse_number(&self.word_length, "word length")?,
+            num_tries: parse_number(&self.num_tries, "number of tries")?,
+            mode: self.mode,
+            language: self.language,
+        })
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -53,28 +329,173 @@ struct RowElements {
 pub struct AppState {
     pub sessions: Arc<RwLock<HashMap<Uuid, GameState>>>,
     pub game_controller: Arc<GameController>,
+    pub word_service: Arc<dyn WordService>,
+    pub reloader: Arc<DictionaryReloader>,
+    pub admin_token: Option<String>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    let app_state = create_app_state(Args::parse()).await?;
-    initialize_server(app_state).await
+    let args = Args::parse();
+
+    match &args.command {
+        Some(Command::Solve { guesses, top }) => {
+            let word_service = create_word_service(&args).await?;
+            cli::solve::run(word_service.as_ref(), args.word_length, guesses, *top).await
+        }
+        Some(Command::Search(query)) => {
+            let word_service = create_word_service(&args).await?;
+            cli::search::run(word_service.as_ref(), query).await
+        }
+        Some(Command::Dict {
+            lengths,
+            max_definition_length,
+        }) => {
+            let word_service = create_word_service(&args).await?;
+            let mut lengths = lengths.clone();
+            lengths.push(args.word_length);
+            lengths.sort_unstable();
+            lengths.dedup();
+            cli::dict::run(word_service.as_ref(), &lengths, *max_definition_length).await
+        }
+        Some(Command::Layers { word }) => {
+            let path = args.layers.as_ref().ok_or_eyre("No --layers file given")?;
+            let base = select_language(&create_base_dictionaries(&args).await?, &args)?;
+            let layered = LayeredWordService::load(base, path)?;
+            cli::layers::run(&layered, word.as_deref()).await
+        }
+        None => {
+            let app_state = create_app_state(args).await?;
+            initialize_server(app_state).await
+        }
+    }
+}
+
+/// Dictionaries of every language, without the layers of `--layers`
+async fn create_base_dictionaries(args: &Args) -> Result<DictionaryRegistry> {
+    let english = DictionaryService::new().await?;
+    let english = match &args.frequency_list {
+        Some(path) => english.with_frequency_list(&FrequencyList::load(path)?),
+        None => english,
+    };
+
+    let mut dictionaries = DictionaryRegistry::empty();
+    dictionaries.register(Arc::new(english));
+    if let Some(directory) = &args.language_dir {
+        dictionaries.load_packs(directory)?;
+    }
+
+    if let Some(endpoint) = &args.remote_dictionary {
+        let local = select_language(&dictionaries, args)?;
+        let mut remote = RemoteWordService::new(endpoint, local.language().clone())?;
+        if let Some(cache_dir) = &args.cache_dir {
+            remote = remote.with_cache_dir(cache_dir.clone());
+        }
+        dictionaries.register(Arc::new(FallbackWordService::new(vec![
+            Arc::new(remote),
+            local,
+        ])?));
+    }
+    Ok(dictionaries)
+}
+
+async fn create_dictionaries(args: &Args) -> Result<DictionaryRegistry> {
+    let mut dictionaries = create_base_dictionaries(args).await?;
+    if let Some(path) = &args.layers {
+        let base = select_language(&dictionaries, args)?;
+        dictionaries.register(Arc::new(LayeredWordService::load(base, path)?));
+    }
+    Ok(dictionaries)
+}
+
+fn select_language(dictionaries: &DictionaryRegistry, args: &Args) -> Result<Arc<dyn WordService>> {
+    dictionaries
+        .get(&args.language)
+        .ok_or_eyre(format!("Unknown language {}", args.language))
+}
+
+#[async_trait]
+impl DictionaryLoader for Args {
+    async fn load(&self) -> Result<DictionaryRegistry> {
+        create_dictionaries(self).await
+    }
+
+    fn sources(&self) -> Vec<PathBuf> {
+        let mut sources: Vec<PathBuf> = [&self.frequency_list, &self.language_dir]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+        if let Some(path) = &self.layers {
+            sources.extend(LayeredWordService::files(path));
+        }
+        sources
+    }
+}
+
+/// Dictionary of the language selected on the command line
+async fn create_word_service(args: &Args) -> Result<Arc<dyn WordService>> {
+    select_language(&create_dictionaries(args).await?, args)
 }
 
 async fn create_app_state(args: Args) -> Result<AppState> {
-    // Create word service
-    let word_service: Arc<dyn WordService> = Arc::new(DictionaryService::new().await?);
+    // Create word services
+    let reloader = Arc::new(DictionaryReloader::new(Box::new(args.clone())).await?);
+    let dictionaries = reloader.registry();
 
     // Create controllers
-    let game_controller = Arc::new(GameController::new(word_service));
+    let mut daily_schedule = DailySchedule::new(GameSettings::from(&args), args.daily_start);
+    if let Some(path) = &args.daily_schedule {
+        daily_schedule = daily_schedule.with_puzzles(ScheduledPuzzle::load(path)?)?;
+    }
+    let rules = RulesRegistry::default();
+    let bounds = SettingsBounds::from(&args);
+    // Games need at least one letter and one row
+    if *bounds.word_lengths.start() == 0 || *bounds.tries.start() == 0 {
+        bail!("The minimum word length and tries must be at least 1");
+    }
+    if args.word_length == 0 || args.num_tries == 0 {
+        bail!("The word length and number of tries must be at least 1");
+    }
+    if bounds.word_lengths.is_empty() || bounds.tries.is_empty() {
+        bail!("The minimum word length and tries can't be above the maximum");
+    }
+    if let Some(mode) = bounds.modes.iter().find(|mode| rules.get(mode).is_none()) {
+        bail!("Unknown game mode {mode} in --player-modes");
+    }
+    let mut game_controller = GameController::new(dictionaries, rules)
+        .with_daily_schedule(daily_schedule)
+        .with_settings_bounds(bounds);
+    if let Some(path) = &args.blocklist {
+        game_controller = game_controller.with_blocklist(Blocklist::load(path)?);
+    }
+    // Blocked words can't be pack words either
+    if let Some(directory) = &args.pack_dir {
+        let mut packs = PackRegistry::empty();
+        packs
+            .load_dir(
+                directory,
+                game_controller.secrets(),
+                game_controller.rules(),
+            )
+            .await?;
+        game_controller = game_controller.with_packs(packs);
+    }
+    // The dictionary browser and search only show words that can be on the screen
+    let word_service = select_language(game_controller.secrets(), &args)?;
+    if let Some(directory) = &args.data_dir {
+        game_controller = game_controller.with_profiles(ProfileStore::open(directory)?);
+    }
+    let game_controller = Arc::new(game_controller);
+    game_controller.validate_daily_schedule().await?;
 
     let sessions = Arc::new(RwLock::new(HashMap::<Uuid, GameState>::new()));
 
     //First session
     let initial_session_id = Uuid::nil();
     let initial_game_state = game_controller
-        .create_new_game(args.num_tries, args.word_length)
+        .create_new_game(&GameSettings::from(&args), None)
         .await?;
     sessions
         .write()
@@ -84,6 +505,9 @@ async fn create_app_state(args: Args) -> Result<AppState> {
     Ok(AppState {
         sessions,
         game_controller,
+        word_service,
+        reloader,
+        admin_token: args.admin_token,
     })
 }
 
@@ -91,7 +515,18 @@ async fn initialize_server(app_state: AppState) -> Result<()> {
     let app = Router::new()
         .route("/", get(root_handler))
         .route("/ws", get(ws_handler))
-        .with_state(app_state);
+        .route("/daily", get(today_handler))
+        .route("/daily/{date}", get(daily_handler))
+        .route("/archive", get(archive_handler))
+        .route("/packs/{id}", get(pack_handler))
+        .route("/api/words", get(search_handler))
+        .route("/dictionary", get(dictionary_handler))
+        .route("/dictionary/{word}", get(word_handler))
+        .route("/admin/reload", post(reload_handler))
+        .route("/admin/schedule", get(schedule_handler))
+        .with_state(app_state.clone());
+
+    spawn_reload_watcher(app_state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
     println!("🚀 Server running on http://{}", listener.local_addr()?);
@@ -105,44 +540,422 @@ async fn initialize_server(app_state: AppState) -> Result<()> {
     Ok(())
 }
 
-async fn root_handler(State(state): State<AppState>) -> Html<String> {
-    match render_root(&state).await {
-        Ok(markup) => Html(markup.into_string()),
-        Err(_) => Html(render_error_page("Failed to load game").into_string()),
+async fn root_handler(
+    State(state): State<AppState>,
+    Query(params): Query<GameParams>,
+    cookies: Option<TypedHeader<Cookie>>,
+) -> Response {
+    let player = player_id(cookies.clone());
+    let mut response = if params.is_empty() {
+        match render_root(&state, player).await {
+            Ok(markup) => Html(markup.into_string()).into_response(),
+            Err(_) => Html(render_error_page("Failed to load game").into_string()).into_response(),
+        }
+    } else {
+        render_linked_game(&state, player, &params).await
+    };
+
+    if player.is_none() {
+        let cookie = format!(
+            "{PLAYER_COOKIE}={}; Path=/; Max-Age={PLAYER_COOKIE_MAX_AGE}; HttpOnly; SameSite=Lax",
+            Uuid::new_v4()
+        );
+        let cookie = HeaderValue::from_str(&cookie).expect("Player cookie is a valid header");
+        response.headers_mut().insert(SET_COOKIE, cookie);
     }
+    response
 }
 
-async fn render_root(state: &AppState) -> Result<Markup> {
-    let game_state = {
-        let sessions = state.sessions.read().await;
-        sessions
-            .get(&Uuid::nil())
-            .expect("Can't find init session")
-            .clone()
+/// Player identified by the cookie set on their first visit
+fn player_id(cookies: Option<TypedHeader<Cookie>>) -> Option<Uuid> {
+    let TypedHeader(cookies) = cookies?;
+    Uuid::parse_str(cookies.get(PLAYER_COOKIE)?).ok()
+}
+
+async fn render_root(state: &AppState, player: Option<Uuid>) -> Result<Markup> {
+    let game_state = initial_game_state(state).await;
+    let picker = PackPicker {
+        packs: state.game_controller.pack_summaries(player),
+        selected: None,
     };
+    let settings = state
+        .game_controller
+        .player_settings(player, &game_state.settings)
+        .await;
+    let panel = state
+        .game_controller
+        .settings_panel(PlayerSettings::from(&settings), None);
 
-    let layout = Layout::new(game_state.render(), "WordGuessr".into());
+    let layout = Layout::new(
+        maud::html! { (picker) (panel) (game_state.render()) },
+        "WordGuessr".into(),
+    );
     Ok(layout.render())
 }
 
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, Arc::new(state)))
+/// Page of a game whose settings are given in the URL, or why it can't be played
+async fn render_linked_game(
+    state: &AppState,
+    player: Option<Uuid>,
+    params: &GameParams,
+) -> Response {
+    let game_state = initial_game_state(state).await;
+    let error = match requested_settings(state, player, params).await {
+        Ok(_) => {
+            let query = serde_urlencoded::to_string(params).unwrap_or_default();
+            let layout = Layout::new(game_state.render(), "WordGuessr".into())
+                .with_socket(format!("/ws?{query}"));
+            return Html(layout.render().into_string()).into_response();
+        }
+        Err(e) => e,
+    };
+
+    // Offer the settings panel with what could be understood of the link
+    let controller = &state.game_controller;
+    let current = PlayerSettings::from(
+        &controller
+            .player_settings(player, &game_state.settings)
+            .await,
+    );
+    let choice = params.apply(current.clone()).unwrap_or(current);
+    let panel = controller.settings_panel(choice, Some(error.to_string()));
+    let layout = Layout::new(
+        maud::html! {
+            div .center-align .padding {
+                h5 { "This game can't be started" }
+                p {
+                    "Change the settings below, or "
+                    a .link href="/" { "play with your own settings" }
+                    "."
+                }
+            }
+            (panel)
+            (game_state.render())
+        },
+        "WordGuessr".into(),
+    );
+    (StatusCode::BAD_REQUEST, Html(layout.render().into_string())).into_response()
 }
 
-async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
-    let (num_tries, word_length) = {
-        let mut sessions = state.sessions.write().await;
-        let game_state = sessions
-            .get_mut(&Uuid::nil())
-            .expect("Can't find init session");
-        (game_state.num_tries, game_state.word_length)
-    };
+/// Settings of a new random game of `player`, with the values given in the URL if any
+async fn requested_settings(
+    state: &AppState,
+    player: Option<Uuid>,
+    params: &GameParams,
+) -> Result<GameSettings> {
+    let defaults = initial_game_state(state).await.settings;
+    let settings = state
+        .game_controller
+        .player_settings(player, &defaults)
+        .await;
+    if params.is_empty() {
+        return Ok(settings);
+    }
 
-    let game_state = state
+    let choice = params.apply(PlayerSettings::from(&settings))?;
+    state.game_controller.check_player_settings(&choice).await?;
+    Ok(choice.apply(&defaults))
+}
+
+/// Game shown before the socket connects, whose settings are used for new games
+async fn initial_game_state(state: &AppState) -> GameState {
+    let sessions = state.sessions.read().await;
+    sessions
+        .get(&Uuid::nil())
+        .expect("Can't find init session")
+        .clone()
+}
+
+async fn pack_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    cookies: Option<TypedHeader<Cookie>>,
+) -> Html<String> {
+    let player = player_id(cookies);
+    let defaults = initial_game_state(&state).await.settings;
+    let settings = state
+        .game_controller
+        .player_settings(player, &defaults)
+        .await;
+    let markup = match state
         .game_controller
-        .create_new_game(num_tries, word_length)
+        .pack_game(&id, &settings, player)
         .await
-        .expect("Can't create new game");
+    {
+        Ok(game_state) => {
+            let picker = PackPicker {
+                packs: state.game_controller.pack_summaries(player),
+                selected: Some(id.clone()),
+            };
+            Layout::new(
+                maud::html! { (picker) (game_state.render()) },
+                "WordGuessr".into(),
+            )
+            .with_socket(format!("/ws?pack={id}"))
+            .render()
+        }
+        Err(e) => render_error_page(&e.to_string()),
+    };
+    Html(markup.into_string())
+}
+
+async fn today_handler(State(state): State<AppState>) -> Html<String> {
+    Html(render_daily(&state, Date::today()).await.into_string())
+}
+
+async fn daily_handler(State(state): State<AppState>, Path(date): Path<String>) -> Html<String> {
+    let markup = match date.parse() {
+        Ok(date) => render_daily(&state, date).await,
+        Err(e) => render_error_page(&e.to_string()),
+    };
+    Html(markup.into_string())
+}
+
+async fn render_daily(state: &AppState, date: Date) -> Markup {
+    match state.game_controller.daily_game(date).await {
+        Ok(game_state) => Layout::new(game_state.render(), "Daily puzzle".into())
+            .with_socket(format!("/ws?daily={date}"))
+            .render(),
+        Err(e) => render_error_page(&e.to_string()),
+    }
+}
+
+async fn archive_handler(
+    State(state): State<AppState>,
+    cookies: Option<TypedHeader<Cookie>>,
+) -> Html<String> {
+    let Some(schedule) = state.game_controller.daily_schedule() else {
+        return Html(render_error_page("There is no daily puzzle").into_string());
+    };
+    let profile = player_id(cookies)
+        .map(|player| state.game_controller.profile(player))
+        .unwrap_or_default();
+
+    let today = Date::today();
+    let archive = DailyArchive {
+        today,
+        streak: profile.daily_streak(today),
+        days: schedule
+            .dates(today)
+            .map(|date| ArchiveDay {
+                date,
+                result: profile.daily_result(date).copied(),
+            })
+            .collect(),
+    };
+    let layout = Layout::new(archive.render(), "Archive".into()).without_socket();
+    Html(layout.render().into_string())
+}
+
+async fn search_handler(
+    State(state): State<AppState>,
+    Query(query): Query<WordQuery>,
+) -> Result<Json<WordPage>, (StatusCode, String)> {
+    state
+        .word_service
+        .search(&query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+async fn dictionary_handler(
+    State(state): State<AppState>,
+    Query(query): Query<WordQuery>,
+) -> Html<String> {
+    let markup = match state.word_service.search(&query).await {
+        Ok(results) => {
+            let browser = DictionaryBrowser { query, results };
+            Layout::new(browser.render(), "Dictionary".into())
+                .without_socket()
+                .render()
+        }
+        Err(e) => render_error_page(&e.to_string()),
+    };
+    Html(markup.into_string())
+}
+
+async fn word_handler(
+    State(state): State<AppState>,
+    Path(word): Path<String>,
+    Query(params): Query<WordParams>,
+) -> Html<String> {
+    let dictionary = match &params.lang {
+        Some(lang) => state.game_controller.secrets().get(lang),
+        None => Some(state.word_service.clone()),
+    };
+    let Some(dictionary) = dictionary else {
+        let lang = params.lang.unwrap_or_default();
+        return Html(render_error_page(&format!("Unknown language {lang}")).into_string());
+    };
+
+    let markup = match render_word(&state, dictionary.as_ref(), &word).await {
+        Ok(Some(markup)) => markup,
+        Ok(None) => render_error_page(&format!("{word} is not in the dictionary")),
+        Err(_) => render_error_page("Failed to load the word"),
+    };
+    Html(markup.into_string())
+}
+
+async fn render_word(
+    state: &AppState,
+    dictionary: &dyn WordService,
+    word: &str,
+) -> Result<Option<Markup>> {
+    let Some(word) = dictionary.get_word(word).await? else {
+        return Ok(None);
+    };
+
+    let detail = WordDetail {
+        usage: state.game_controller.word_usage(&word.word),
+        related: dictionary.related_words(&word).await?,
+        language: dictionary.language().id.clone(),
+        word,
+    };
+    let layout = Layout::new(detail.render(), detail.word.word.clone()).without_socket();
+    Ok(Some(layout.render()))
+}
+
+/// Reloads the dictionaries whenever their files change
+fn spawn_reload_watcher(state: AppState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(RELOAD_POLL_INTERVAL).await;
+            if state.reloader.sources_changed() {
+                match reload_dictionaries(&state).await {
+                    Ok(summary) => println!("{summary}"),
+                    Err(e) => eprintln!("Keeping the current dictionaries: {e:?}"),
+                }
+            }
+        }
+    });
+}
+
+async fn reload_dictionaries(state: &AppState) -> Result<String> {
+    let reloaded = state.reloader.reload().await?;
+    state.game_controller.forget_openers();
+
+    let sizes: Vec<String> = reloaded
+        .iter()
+        .map(|d| format!("{} ({} words)", d.language, d.words))
+        .collect();
+    Ok(format!("Reloaded the dictionaries: {}", sizes.join(", ")))
+}
+
+/// Response refusing the request unless it has the admin token, given as a bearer token or,
+/// so that browsers can ask for it, as the password of basic authentication
+fn admin_rejection(state: &AppState, headers: &HeaderMap) -> Option<Response> {
+    let Some(token) = &state.admin_token else {
+        return Some((StatusCode::NOT_FOUND, "Admin endpoints are disabled").into_response());
+    };
+    let bearer = headers.typed_get::<Authorization<Bearer>>();
+    let basic = headers.typed_get::<Authorization<Basic>>();
+    if bearer.is_some_and(|auth| auth.token() == token)
+        || basic.is_some_and(|auth| auth.password() == token)
+    {
+        return None;
+    }
+
+    let mut response = (StatusCode::UNAUTHORIZED, "Invalid admin token").into_response();
+    response.headers_mut().insert(
+        WWW_AUTHENTICATE,
+        HeaderValue::from_static("Basic realm=\"WordGuessr admin\""),
+    );
+    Some(response)
+}
+
+async fn reload_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(response) = admin_rejection(&state, &headers) {
+        return response;
+    }
+
+    match reload_dictionaries(&state).await {
+        Ok(summary) => (StatusCode::OK, summary).into_response(),
+        Err(e) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Keeping the current dictionaries: {e:#}"),
+        )
+            .into_response(),
+    }
+}
+
+async fn schedule_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<PreviewParams>,
+) -> Response {
+    if let Some(response) = admin_rejection(&state, &headers) {
+        return response;
+    }
+
+    let today = Date::today();
+    let days = params.days.clamp(1, MAX_PREVIEW_DAYS);
+    let markup = match state.game_controller.daily_preview(today, days).await {
+        Ok(days) => {
+            let preview = SchedulePreview { today, days };
+            Layout::new(preview.render(), "Daily schedule".into())
+                .without_socket()
+                .render()
+        }
+        Err(e) => render_error_page(&e.to_string()),
+    };
+    Html(markup.into_string()).into_response()
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(params): Query<SocketParams>,
+    cookies: Option<TypedHeader<Cookie>>,
+) -> impl IntoResponse {
+    let player = player_id(cookies);
+    ws.on_upgrade(move |socket| handle_socket(socket, Arc::new(state), player, params))
+}
+
+async fn handle_socket(
+    mut socket: WebSocket,
+    state: Arc<AppState>,
+    player: Option<Uuid>,
+    params: SocketParams,
+) {
+    let defaults = initial_game_state(&state).await.settings;
+    let settings = state
+        .game_controller
+        .player_settings(player, &defaults)
+        .await;
+    let game_state = match (params.daily, &params.pack) {
+        (Some(date), _) => state.game_controller.create_daily_game(date, player).await,
+        (None, Some(pack)) => {
+            state
+                .game_controller
+                .create_pack_game(pack, &settings, player)
+                .await
+        }
+        (None, None) => {
+            let settings = requested_settings(&state, player, &params.game)
+                .await
+                .unwrap_or(settings);
+            state
+                .game_controller
+                .create_new_game(&settings, player)
+                .await
+        }
+    };
+    let game_state = match game_state {
+        Ok(game_state) => game_state,
+        Err(e) => {
+            // The daily date and pack come from the socket URL, and may not exist
+            let page = render_error_page(&e.to_string()).into_string();
+            let _ = socket.send(Message::Text(page.into())).await;
+            return;
+        }
+    };
+
+    // The page shows a preview game, pack words don't all have its length
+    let initial = game_state.render().into_string();
+    if socket.send(Message::Text(initial.into())).await.is_err() {
+        return;
+    }
 
     let session_id = Uuid::new_v4();
     {
@@ -150,14 +963,28 @@ async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
         sessions.insert(session_id, game_state);
     }
 
-    while let Some(Ok(msg)) = socket.next().await {
-        match process_message(&state, msg, session_id).await {
-            ControlFlow::Break(()) => break,
-            ControlFlow::Continue(html) => {
+    // Markup computed in the background, pushed to the player when ready
+    let (updates_tx, mut updates_rx) = mpsc::unbounded_channel::<Markup>();
+
+    loop {
+        tokio::select! {
+            msg = socket.next() => {
+                let Some(Ok(msg)) = msg else { break };
+                match process_message(&state, msg, session_id, &updates_tx).await {
+                    ControlFlow::Break(()) => break,
+                    ControlFlow::Continue(html) => {
+                        socket
+                            .send(Message::Text(html.0.into()))
+                            .await
+                            .expect("Couldn't send HTML page");
+                    }
+                }
+            }
+            Some(markup) = updates_rx.recv() => {
                 socket
-                    .send(Message::Text(html.0.into()))
+                    .send(Message::Text(markup.into_string().into()))
                     .await
-                    .expect("Couldn't send HTML page");
+                    .expect("Couldn't send HTML update");
             }
         }
     }
@@ -169,6 +996,7 @@ async fn process_message(
     state: &Arc<AppState>,
     msg: Message,
     session_id: Uuid,
+    updates: &UnboundedSender<Markup>,
 ) -> ControlFlow<(), Html<String>> {
     let msg = match msg {
         Message::Text(t) => t,
@@ -178,8 +1006,14 @@ async fn process_message(
 
     let markup = if msg.contains("\"reset\"") {
         handle_reset(state, session_id).await
+    } else if let Ok(request) = serde_json::from_str::<HintRequest>(&msg) {
+        handle_hint(state, session_id, request.hint).await
+    } else if msg.contains("\"stats\"") {
+        handle_stats(state, session_id).await
+    } else if let Ok(form) = serde_json::from_str::<SettingsForm>(&msg) {
+        handle_settings(state, session_id, form).await
     } else if let Ok(input) = serde_json::from_str::<RowElements>(&msg) {
-        handle_input(state, session_id, input).await
+        handle_input(state, session_id, input, updates).await
     } else {
         render_error_page("Invalid message format")
     };
@@ -187,44 +1021,232 @@ async fn process_message(
     ControlFlow::Continue(Html(markup.into_string()))
 }
 
-async fn handle_input(state: &AppState, session_id: Uuid, input: RowElements) -> Markup {
+async fn handle_input(
+    state: &AppState,
+    session_id: Uuid,
+    input: RowElements,
+    updates: &UnboundedSender<Markup>,
+) -> Markup {
+    // Scored on a copy, as some modes go through the whole dictionary, so that other
+    // sessions aren't kept waiting on the sessions lock
+    let mut snapshot = {
+        let sessions = state.sessions.read().await;
+        let Some(game_state) = sessions.get(&session_id) else {
+            return render_error_page("Session not found");
+        };
+        game_state.clone()
+    };
+    let result = state
+        .game_controller
+        .process_guess(&mut snapshot, input.input)
+        .await;
+
+    let Ok(result) = result else {
+        return render_error_page("Failed to process guess");
+    };
+
+    // The guess changed nothing, and the session may have moved on since the copy, e.g. with
+    // the analysis of the game
+    if let GuessResult::InvalidWord | GuessResult::GameAlreadyOver = result {
+        return match state.sessions.read().await.get(&session_id) {
+            Some(game_state) => game_state.render(),
+            None => render_error_page("Session not found"),
+        };
+    }
+
+    // Filter the dictionary without holding the sessions lock
+    match state.game_controller.remaining_words(&snapshot).await {
+        Ok(words) => snapshot.remaining_words = Some(words),
+        Err(e) => eprintln!("Error counting remaining words: {e:?}"),
+    }
+
     let mut sessions = state.sessions.write().await;
-    if let Some(game_state) = sessions.get_mut(&session_id) {
-        return match state
-            .game_controller
-            .process_guess(game_state, input.input)
-            .await
-        {
-            Ok(_) => game_state.render(),
-            Err(_) => render_error_page("Failed to process guess"),
+    let Some(game_state) = sessions.get_mut(&session_id) else {
+        return render_error_page("Session not found");
+    };
+    // Unless another game was started in the meantime
+    if game_state.id != snapshot.id {
+        return game_state.render();
+    }
+    *game_state = snapshot;
+
+    if let GuessResult::Won | GuessResult::Lost = result {
+        spawn_analysis(
+            state.clone(),
+            session_id,
+            game_state.clone(),
+            updates.clone(),
+        );
+
+        // Show the statistics with the game just finished
+        let highlight =
+            matches!(result, GuessResult::Won).then_some(game_state.grid.current_row + 1);
+        return maud::html! {
+            (game_state.render())
+            (stats_dialog(state, game_state, highlight))
         };
     }
 
-    render_error_page("Session not found")
+    game_state.render()
 }
 
-async fn handle_reset(state: &AppState, session_id: Uuid) -> Markup {
-    let mut sessions = state.sessions.write().await;
-    if let Some(game_state) = sessions.get_mut(&session_id) {
-        return match state
-            .game_controller
-            .create_new_game(game_state.num_tries, game_state.word_length)
-            .await
-        {
-            Ok(new_game_state) => {
-                *game_state = new_game_state;
-                game_state.render()
-            }
+/// Statistics dialog of the player of `game_state`, nothing for players without a profile
+fn stats_dialog(state: &AppState, game_state: &GameState, highlight: Option<usize>) -> Markup {
+    let Some(player) = game_state.player else {
+        return maud::html! {};
+    };
+    let dialog = StatsDialog {
+        stats: state.game_controller.player_stats(player),
+        highlight,
+    };
+    maud::html! {
+        div #stats-container { (dialog) }
+    }
+}
+
+async fn handle_stats(state: &AppState, session_id: Uuid) -> Markup {
+    let sessions = state.sessions.read().await;
+    match sessions.get(&session_id) {
+        Some(game_state) => stats_dialog(state, game_state, None),
+        None => render_error_page("Session not found"),
+    }
+}
+
+/// Analyses a finished game off the request path and pushes the result to the player
+fn spawn_analysis(
+    state: AppState,
+    session_id: Uuid,
+    game_state: GameState,
+    updates: UnboundedSender<Markup>,
+) {
+    tokio::spawn(async move {
+        let analysis = match state.game_controller.analyze_game(&game_state).await {
+            Ok(analysis) => analysis,
             Err(e) => {
-                eprintln!("Error creating new game in handle_reset: {e:?}");
-                render_error_page("Failed to create a new game")
+                eprintln!("Error analysing game: {e:?}");
+                return;
             }
         };
+
+        let markup = maud::html! {
+            div #analysis-container hx-swap-oob="true" { (analysis) }
+        };
+
+        // Skip the update if the player has started another game in the meantime
+        let mut sessions = state.sessions.write().await;
+        if let Some(current) = sessions.get_mut(&session_id)
+            && current.id == game_state.id
+        {
+            current.analysis = Some(analysis);
+            // The socket may already be closed, in which case there is no one to notify
+            let _ = updates.send(markup);
+        }
+    });
+}
+
+/// Starts a game with the settings chosen by the player, who keeps them for their next games
+async fn handle_settings(state: &AppState, session_id: Uuid, form: SettingsForm) -> Markup {
+    let defaults = initial_game_state(state).await.settings;
+    let Some(current) = session_copy(state, session_id).await else {
+        return render_error_page("Session not found");
+    };
+
+    let controller = &state.game_controller;
+    let choice = match form.parse() {
+        Ok(choice) => choice,
+        Err(e) => {
+            let current = PlayerSettings::from(&current.settings);
+            return controller
+                .settings_panel(current, Some(e.to_string()))
+                .render();
+        }
+    };
+    let settings = match controller
+        .choose_settings(current.player, choice.clone(), &defaults)
+        .await
+    {
+        Ok(settings) => settings,
+        Err(e) => {
+            return controller
+                .settings_panel(choice, Some(e.to_string()))
+                .render();
+        }
+    };
+
+    match controller.create_new_game(&settings, current.player).await {
+        Ok(new_game_state) => maud::html! {
+            (controller.settings_panel(choice, None))
+            (replace_game(state, session_id, current.id, new_game_state).await)
+        },
+        Err(e) => {
+            eprintln!("Error creating new game in handle_settings: {e:?}");
+            render_error_page("Failed to create a new game")
+        }
+    }
+}
+
+/// Copy of the game of a session, so that slow work on it doesn't hold the sessions lock
+async fn session_copy(state: &AppState, session_id: Uuid) -> Option<GameState> {
+    state.sessions.read().await.get(&session_id).cloned()
+}
+
+/// Puts `new_game_state` in place of the game `previous_id` of the session, unless another
+/// game was started in the meantime, and renders the game of the session
+async fn replace_game(
+    state: &AppState,
+    session_id: Uuid,
+    previous_id: Uuid,
+    new_game_state: GameState,
+) -> Markup {
+    let mut sessions = state.sessions.write().await;
+    let Some(game_state) = sessions.get_mut(&session_id) else {
+        return render_error_page("Session not found");
+    };
+    if game_state.id == previous_id {
+        *game_state = new_game_state;
+    }
+    game_state.render()
+}
+
+async fn handle_hint(state: &AppState, session_id: Uuid, kind: HintKind) -> Markup {
+    let mut sessions = state.sessions.write().await;
+    if let Some(game_state) = sessions.get_mut(&session_id) {
+        state.game_controller.request_hint(game_state, kind);
+        return game_state.render();
     }
 
     render_error_page("Session not found")
 }
 
+async fn handle_reset(state: &AppState, session_id: Uuid) -> Markup {
+    let Some(current) = session_copy(state, session_id).await else {
+        return render_error_page("Session not found");
+    };
+
+    // A daily puzzle is replayed, other games get a new secret
+    let controller = &state.game_controller;
+    let new_game_state = match (current.daily, &current.pack) {
+        (Some(date), _) => controller.create_daily_game(date, current.player).await,
+        (None, Some(pack)) => {
+            controller
+                .create_pack_game(pack, &current.settings, current.player)
+                .await
+        }
+        (None, None) => {
+            controller
+                .create_new_game(&current.settings, current.player)
+                .await
+        }
+    };
+    match new_game_state {
+        Ok(new_game_state) => replace_game(state, session_id, current.id, new_game_state).await,
+        Err(e) => {
+            eprintln!("Error creating new game in handle_reset: {e:?}");
+            render_error_page("Failed to create a new game")
+        }
+    }
+}
+
 fn render_error_page(message: &str) -> Markup {
     use maud::html;
 
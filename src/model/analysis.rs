@@ -0,0 +1,55 @@
+/// How a single guess compared with the best possible one at that point of the game
+#[derive(Clone, Debug)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    /// Words that could still be the secret before the guess
+    pub candidates_before: usize,
+    /// Words that could still be the secret after the feedback
+    pub candidates_after: usize,
+    /// Guess with the highest expected information
+    pub best_guess: String,
+    pub best_entropy: f64,
+    /// Expected information of the guess that was played, in bits
+    pub entropy: f64,
+    /// Information the feedback actually gave, in bits
+    pub information: f64,
+}
+
+impl GuessAnalysis {
+    /// Expected information of the guess relative to the best one, from 0 to 100
+    pub fn skill(&self) -> u8 {
+        // Only one word left: the best play is to guess it
+        if self.best_entropy <= 0.0 {
+            return if self.guess == self.best_guess {
+                100
+            } else {
+                0
+            };
+        }
+        (self.entropy / self.best_entropy * 100.0)
+            .clamp(0.0, 100.0)
+            .round() as u8
+    }
+
+    /// Bits gained above (or below) what the guess was expected to give
+    pub fn luck(&self) -> f64 {
+        self.information - self.entropy
+    }
+}
+
+/// Per-guess analysis of a finished game
+#[derive(Clone, Debug)]
+pub struct Analysis {
+    pub guesses: Vec<GuessAnalysis>,
+}
+
+impl Analysis {
+    /// Average skill over all guesses
+    pub fn skill(&self) -> u8 {
+        if self.guesses.is_empty() {
+            return 0;
+        }
+        let total: usize = self.guesses.iter().map(|g| g.skill() as usize).sum();
+        (total / self.guesses.len()) as u8
+    }
+}
@@ -0,0 +1,16 @@
+use crate::model::{date::Date, profile::DailyResult};
+
+/// Past daily puzzles and how the player did on them
+pub struct DailyArchive {
+    pub today: Date,
+    /// Daily puzzles won in a row on their day
+    pub streak: usize,
+    /// Most recent first
+    pub days: Vec<ArchiveDay>,
+}
+
+pub struct ArchiveDay {
+    pub date: Date,
+    /// First outcome of the player, if they played it
+    pub result: Option<DailyResult>,
+}
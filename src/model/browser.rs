@@ -0,0 +1,28 @@
+use crate::{
+    model::usage::WordUsage,
+    service::{
+        dictionary::Word,
+        search::{WordPage, WordQuery},
+    },
+};
+
+/// Search form and results of the dictionary pages
+pub struct DictionaryBrowser {
+    pub query: WordQuery,
+    pub results: WordPage,
+}
+
+/// Page of `word` in the dictionary of `language`
+pub fn word_url(word: &str, language: &str) -> String {
+    format!("/dictionary/{word}?lang={language}")
+}
+
+/// Everything known about a dictionary word
+pub struct WordDetail {
+    pub word: Word,
+    /// Id of the language of the dictionary the word is from
+    pub language: String,
+    pub usage: WordUsage,
+    /// Dictionary words used in its definitions
+    pub related: Vec<String>,
+}
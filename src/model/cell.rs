@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum CellState {
     Empty,
     Correct,
     Present,
     Absent,
+    /// Scored, but the mode doesn't reveal the result of this letter
+    Concealed,
 }
 
 #[derive(Clone, Debug)]
@@ -13,6 +15,10 @@ pub struct Cell {
     pub letter: Option<char>,
     pub state: CellState,
     pub is_disabled: bool,
+    /// The letter was revealed by a hint
+    pub is_hinted: bool,
+    /// How many times the letter occurs in the secret word, for confirmed duplicate letters
+    pub occurrences: Option<usize>,
 }
 
 impl Cell {
@@ -20,6 +26,8 @@ impl Cell {
         Self {
             letter,
             is_disabled,
+            is_hinted: false,
+            occurrences: None,
             state: CellState::Empty,
         }
     }
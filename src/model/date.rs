@@ -0,0 +1,196 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Years a date can be in, which keeps the day arithmetic far from overflowing
+const YEARS: std::ops::RangeInclusive<i64> = 1..=9999;
+
+/// Calendar day in UTC, such as the day of a daily puzzle
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Date {
+    /// Days since 1970-01-01
+    days: i64,
+}
+
+impl Date {
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Option<Self> {
+        if !YEARS.contains(&year)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return None;
+        }
+        Some(Self {
+            days: days_from_civil(year, month, day),
+        })
+    }
+
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Self {
+            days: (seconds / SECONDS_PER_DAY) as i64,
+        }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self {
+            days: self.days + days,
+        }
+    }
+
+    /// Number of days from `earlier` to this date
+    pub fn days_since(self, earlier: Date) -> i64 {
+        self.days - earlier.days
+    }
+
+    pub fn ymd(self) -> (i64, u32, u32) {
+        civil_from_days(self.days)
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a date of the proleptic Gregorian calendar, counted in 400-year eras
+/// that start on March 1st so that leap days fall at the end of the year
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = (i64::from(month) + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseDateError(String);
+
+impl Display for ParseDateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+/// Parses `YYYY-MM-DD`
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseDateError(format!("Invalid date {s}, expected YYYY-MM-DD"));
+        let mut parts = s.trim().splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let (Ok(year), Ok(month), Ok(day)) = (year.parse(), month.parse(), day.parse()) else {
+            return Err(invalid());
+        };
+        Self::from_ymd(year, month, day).ok_or_else(invalid)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        date.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_round_trip() {
+        for days in (-800_000..800_000).step_by(97) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+
+        assert!(Date::from_ymd(2024, 2, 29).is_some());
+        assert!(Date::from_ymd(2000, 2, 29).is_some());
+        assert!(Date::from_ymd(1900, 2, 29).is_none());
+        assert_eq!(
+            Date::from_ymd(2024, 2, 28).map(|d| d.add_days(1).to_string()),
+            Some("2024-02-29".to_string())
+        );
+        assert_eq!(
+            Date::from_ymd(2023, 2, 28).map(|d| d.add_days(1).to_string()),
+            Some("2023-03-01".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        let date: Date = "2026-10-18".parse().unwrap();
+        assert_eq!(date.to_string(), "2026-10-18");
+        assert!("2026-13-01".parse::<Date>().is_err());
+        assert!("2026-10".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn rejects_years_out_of_range() {
+        assert!("999999999999999999-01-01".parse::<Date>().is_err());
+        assert!(Date::from_ymd(0, 1, 1).is_none());
+        assert!(Date::from_ymd(10_000, 1, 1).is_none());
+        assert!(Date::from_ymd(9999, 12, 31).is_some());
+    }
+}
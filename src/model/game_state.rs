@@ -1,12 +1,39 @@
-use crate::{model::grid::Grid, service::dictionary::Word};
+use uuid::Uuid;
+
+use crate::{
+    model::{analysis::Analysis, date::Date, grid::Grid, hint::Hint, settings::GameSettings},
+    service::{dictionary::Word, language::Language},
+};
+
+/// Points for a win, multiplied by the number of tries left including the winning one
+const POINTS_PER_TRY: usize = 100;
 
 #[derive(Clone, Debug)]
 pub struct GameState {
+    pub id: Uuid,
+    /// Player the game is for, when known from their cookie
+    pub player: Option<Uuid>,
+    /// Day of the daily puzzle this game is
+    pub daily: Option<Date>,
+    /// Id of the puzzle pack the secret comes from
+    pub pack: Option<String>,
+    /// Theme of a scheduled daily puzzle
+    pub theme: Option<String>,
+    /// Hint given with a scheduled daily puzzle
+    pub daily_hint: Option<String>,
     pub grid: Grid,
     pub secret_word: Word,
-    pub word_length: usize,
-    pub num_tries: usize,
+    pub settings: GameSettings,
+    /// Language of the secret word, whose alphabet the cells accept
+    pub language: Language,
     pub status: GameStatus,
+    pub hints: Vec<Hint>,
+    /// Shown before the first guess in modes that give one
+    pub clue: Option<String>,
+    /// Words still consistent with the feedback, updated after each scored row
+    pub remaining_words: Option<Vec<String>>,
+    /// Computed in the background once the game is over
+    pub analysis: Option<Analysis>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -17,17 +44,55 @@ pub enum GameStatus {
 }
 
 impl GameState {
-    pub fn new(secret_word: Word, num_tries: usize, word_length: usize) -> Self {
+    pub fn new(secret_word: Word, settings: GameSettings, language: Language) -> Self {
         Self {
-            grid: Grid::new(num_tries, word_length),
+            id: Uuid::new_v4(),
+            player: None,
+            daily: None,
+            pack: None,
+            theme: None,
+            daily_hint: None,
+            grid: Grid::new(settings.num_tries, settings.word_length),
             secret_word,
-            word_length,
-            num_tries,
+            settings,
+            language,
             status: GameStatus::Playing,
+            hints: Vec::new(),
+            clue: None,
+            remaining_words: None,
+            analysis: None,
         }
     }
 
     pub fn is_game_over(&self) -> bool {
         matches!(self.status, GameStatus::Won | GameStatus::Lost)
     }
+
+    pub fn hints_left(&self) -> usize {
+        self.settings.max_hints.saturating_sub(self.hints.len())
+    }
+
+    /// Shows the revealed letters in the row being played
+    pub fn apply_hints(&mut self) {
+        let row = &mut self.grid.rows[self.grid.current_row];
+        for hint in &self.hints {
+            if let Hint::Letter { position, letter } = hint
+                && let Some(cell) = row.cells.get_mut(*position)
+            {
+                cell.letter = Some(*letter);
+                cell.is_hinted = true;
+            }
+        }
+    }
+
+    /// Final score of the game: nothing for a loss, otherwise the tries left minus the hint costs
+    pub fn score(&self) -> usize {
+        if self.status != GameStatus::Won {
+            return 0;
+        }
+
+        let tries_left = self.settings.num_tries - self.grid.current_row;
+        let hint_costs: usize = self.hints.iter().map(Hint::cost).sum();
+        (tries_left * POINTS_PER_TRY).saturating_sub(hint_costs)
+    }
 }
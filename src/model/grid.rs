@@ -20,6 +20,11 @@ impl Grid {
         }
     }
 
+    /// Rows that already hold a scored guess
+    pub fn scored_rows(&self) -> impl Iterator<Item = &Row> {
+        self.rows.iter().filter(|row| row.is_scored())
+    }
+
     pub fn can_advance(&self) -> bool {
         self.current_row < self.num_rows - 1
     }
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+/// Points subtracted from the final score for a revealed letter
+pub const LETTER_HINT_COST: usize = 50;
+/// Points subtracted from the final score for a definition clue
+pub const DEFINITION_HINT_COST: usize = 30;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Hint {
+    /// A letter of the secret word in its correct position
+    Letter { position: usize, letter: char },
+    /// One of the definitions of the secret word, with the word masked out
+    Definition(String),
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum HintKind {
+    Letter,
+    Definition,
+}
+
+impl Hint {
+    pub fn cost(&self) -> usize {
+        match self {
+            Hint::Letter { .. } => LETTER_HINT_COST,
+            Hint::Definition(_) => DEFINITION_HINT_COST,
+        }
+    }
+}
@@ -1,16 +1,31 @@
-use crate::{model::game_state::GameStatus, service::dictionary::Word};
+use crate::{
+    model::{analysis::Analysis, game_state::GameStatus},
+    service::dictionary::Word,
+};
 
 #[derive(Clone)]
 pub struct Message {
     pub status: GameStatus,
     pub secret_word: Word,
+    /// Id of the language of the secret word
+    pub language: String,
+    pub score: usize,
+    pub analysis: Option<Analysis>,
 }
 
 impl Message {
-    pub fn new(status: GameStatus, secret_word: Word) -> Self {
+    pub fn new(status: GameStatus, secret_word: Word, language: String, score: usize) -> Self {
         Self {
             status,
             secret_word,
+            language,
+            score,
+            analysis: None,
         }
     }
+
+    pub fn with_analysis(mut self, analysis: Option<Analysis>) -> Self {
+        self.analysis = analysis;
+        self
+    }
 }
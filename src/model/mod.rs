@@ -1,5 +1,16 @@
+pub mod analysis;
+pub mod archive;
+pub mod browser;
 pub mod cell;
+pub mod date;
 pub mod game_state;
 pub mod grid;
+pub mod hint;
 pub mod message;
+pub mod pack;
+pub mod profile;
 pub mod row;
+pub mod schedule;
+pub mod settings;
+pub mod stats;
+pub mod usage;
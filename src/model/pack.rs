@@ -0,0 +1,16 @@
+/// A puzzle pack and how far the player got through it
+pub struct PackSummary {
+    pub id: String,
+    pub name: String,
+    pub theme: Option<String>,
+    /// Words of the pack the player has found
+    pub solved: usize,
+    pub total: usize,
+}
+
+/// Choice of puzzle pack on the start screen
+pub struct PackPicker {
+    pub packs: Vec<PackSummary>,
+    /// Pack being played, none for random words
+    pub selected: Option<String>,
+}
@@ -0,0 +1,99 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{date::Date, settings::PlayerSettings, stats::PlayerStats};
+
+/// Outcome of a daily puzzle
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct DailyResult {
+    pub won: bool,
+    /// Rows played
+    pub tries: usize,
+    /// Whether it was played on its day rather than from the archive, only those count
+    /// towards the streak
+    pub on_the_day: bool,
+}
+
+/// What is remembered about a player across games
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct PlayerProfile {
+    /// Secret words of the player's past games, by language and word length
+    #[serde(default)]
+    pub seen_secrets: HashMap<String, HashMap<usize, HashSet<String>>>,
+    /// First outcome of each daily puzzle played, by day (YYYY-MM-DD)
+    #[serde(default)]
+    pub daily: BTreeMap<String, DailyResult>,
+    /// Words solved in each puzzle pack
+    #[serde(default)]
+    pub packs: HashMap<String, HashSet<String>>,
+    /// Settings of the player's random games, the server defaults until they choose
+    #[serde(default)]
+    pub settings: Option<PlayerSettings>,
+    /// Outcome of every game played, except replays of daily puzzles
+    #[serde(default)]
+    pub stats: PlayerStats,
+}
+
+impl PlayerProfile {
+    pub fn has_seen(&self, language: &str, length: usize, word: &str) -> bool {
+        self.seen_secrets
+            .get(language)
+            .and_then(|lengths| lengths.get(&length))
+            .is_some_and(|words| words.contains(word))
+    }
+
+    pub fn mark_seen(&mut self, language: &str, length: usize, word: &str) {
+        self.seen_secrets
+            .entry(language.to_string())
+            .or_default()
+            .entry(length)
+            .or_default()
+            .insert(word.to_string());
+    }
+
+    /// Starts over once every word of a length has been played
+    pub fn forget_seen(&mut self, language: &str, length: usize) {
+        if let Some(lengths) = self.seen_secrets.get_mut(language) {
+            lengths.remove(&length);
+        }
+    }
+
+    pub fn daily_result(&self, date: Date) -> Option<&DailyResult> {
+        self.daily.get(&date.to_string())
+    }
+
+    /// Keeps the first outcome, so that replays don't change it
+    pub fn record_daily(&mut self, date: Date, result: DailyResult) {
+        self.daily.entry(date.to_string()).or_insert(result);
+    }
+
+    /// Daily puzzles won on their day in a row, up to today or yesterday while today's
+    /// puzzle is still to be played
+    pub fn daily_streak(&self, today: Date) -> usize {
+        let won_on_the_day = |date: Date| {
+            self.daily_result(date)
+                .is_some_and(|result| result.won && result.on_the_day)
+        };
+        let last = if self.daily_result(today).is_some() {
+            today
+        } else {
+            today.add_days(-1)
+        };
+        (0..)
+            .map(|days| last.add_days(-days))
+            .take_while(|&date| won_on_the_day(date))
+            .count()
+    }
+
+    pub fn solved_in_pack(&self, pack: &str) -> Option<&HashSet<String>> {
+        self.packs.get(pack)
+    }
+
+    pub fn mark_solved(&mut self, pack: &str, word: &str) {
+        self.packs
+            .entry(pack.to_string())
+            .or_default()
+            .insert(word.to_string());
+    }
+}
@@ -1,19 +1,63 @@
-use crate::model::cell::Cell;
+use crate::model::cell::{Cell, CellState};
 
 #[derive(Clone, Debug)]
 pub struct Row {
     pub cells: Vec<Cell>,
+    pub summary: Option<RowSummary>,
     is_disabled: bool,
 }
 
+/// Number of correct and present letters of a scored row
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RowSummary {
+    pub correct: usize,
+    pub present: usize,
+}
+
+impl From<&[CellState]> for RowSummary {
+    fn from(states: &[CellState]) -> Self {
+        Self {
+            correct: states.iter().filter(|s| **s == CellState::Correct).count(),
+            present: states.iter().filter(|s| **s == CellState::Present).count(),
+        }
+    }
+}
+
 impl Row {
     pub fn new(length: usize, is_disabled: bool) -> Self {
         Self {
             cells: vec![Cell::new(None, is_disabled); length],
+            summary: None,
             is_disabled,
         }
     }
 
+    /// Replaces the cells with a scored guess
+    pub fn fill(&mut self, guess: &[char], states: &[CellState]) {
+        self.cells = guess
+            .iter()
+            .zip(states.iter())
+            .zip(self.cells.iter())
+            .map(|((letter, state), previous)| {
+                let mut cell = Cell::new(Some(*letter), self.is_disabled).with_state(state.clone());
+                cell.is_hinted = previous.is_hinted && previous.letter == Some(*letter);
+                cell
+            })
+            .collect();
+    }
+
+    pub fn letters(&self) -> Vec<char> {
+        self.cells.iter().filter_map(|cell| cell.letter).collect()
+    }
+
+    pub fn states(&self) -> Vec<CellState> {
+        self.cells.iter().map(|cell| cell.state.clone()).collect()
+    }
+
+    pub fn is_scored(&self) -> bool {
+        self.cells.iter().all(|cell| cell.state != CellState::Empty)
+    }
+
     pub fn set_disabled(&mut self, is_disabled: bool) {
         self.is_disabled = is_disabled;
         self.cells
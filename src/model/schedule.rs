@@ -0,0 +1,16 @@
+use crate::model::date::Date;
+
+/// Upcoming daily puzzles, as shown to administrators
+pub struct SchedulePreview {
+    pub today: Date,
+    pub days: Vec<PreviewDay>,
+}
+
+pub struct PreviewDay {
+    pub date: Date,
+    pub word: String,
+    /// Whether the word comes from the schedule file rather than the dictionary
+    pub scheduled: bool,
+    pub theme: Option<String>,
+    pub hint: Option<String>,
+}
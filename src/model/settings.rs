@@ -0,0 +1,102 @@
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::service::commonness::Difficulty;
+
+/// Parameters a new game is created with
+#[derive(Clone, Debug)]
+pub struct GameSettings {
+    pub word_length: usize,
+    pub num_tries: usize,
+    /// Id of the game mode, see `RulesRegistry`
+    pub mode: String,
+    /// Id of the language of the secret word, see `DictionaryRegistry`
+    pub language: String,
+    /// Number of hints a player can request in a game
+    pub max_hints: usize,
+    /// Show how many times confirmed letters occur in the secret word
+    pub letter_counts: bool,
+    /// List the words still consistent with the feedback once the game is over
+    pub reveal_remaining: bool,
+    /// How common the secret word is
+    pub difficulty: Difficulty,
+}
+
+/// What players choose for their own games, within the `SettingsBounds`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlayerSettings {
+    pub word_length: usize,
+    pub num_tries: usize,
+    pub mode: String,
+    pub language: String,
+}
+
+impl PlayerSettings {
+    /// The settings of a game with this choice, the rest is taken from `defaults`
+    pub fn apply(&self, defaults: &GameSettings) -> GameSettings {
+        GameSettings {
+            word_length: self.word_length,
+            num_tries: self.num_tries,
+            mode: self.mode.clone(),
+            language: self.language.clone(),
+            ..defaults.clone()
+        }
+    }
+}
+
+impl From<&GameSettings> for PlayerSettings {
+    fn from(settings: &GameSettings) -> Self {
+        Self {
+            word_length: settings.word_length,
+            num_tries: settings.num_tries,
+            mode: settings.mode.clone(),
+            language: settings.language.clone(),
+        }
+    }
+}
+
+/// Limits set by the administrator on what players can choose
+#[derive(Clone, Debug)]
+pub struct SettingsBounds {
+    pub word_lengths: RangeInclusive<usize>,
+    pub tries: RangeInclusive<usize>,
+    /// Ids of the modes players can choose, every mode when empty
+    pub modes: Vec<String>,
+    /// Ids of the languages players can choose, every language when empty
+    pub languages: Vec<String>,
+}
+
+impl SettingsBounds {
+    pub fn allows_mode(&self, mode: &str) -> bool {
+        self.modes.is_empty() || self.modes.iter().any(|id| id == mode)
+    }
+
+    pub fn allows_language(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|id| id == language)
+    }
+}
+
+impl Default for SettingsBounds {
+    /// No limits
+    fn default() -> Self {
+        Self {
+            word_lengths: 1..=usize::MAX,
+            tries: 1..=usize::MAX,
+            modes: Vec::new(),
+            languages: Vec::new(),
+        }
+    }
+}
+
+/// Settings panel of the start screen
+pub struct SettingsPanel {
+    pub choice: PlayerSettings,
+    pub bounds: SettingsBounds,
+    /// Id and name of the modes players can choose
+    pub modes: Vec<(String, String)>,
+    /// Id and name of the languages players can choose
+    pub languages: Vec<(String, String)>,
+    /// Why the last choice was refused
+    pub error: Option<String>,
+}
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a player's finished games
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct PlayerStats {
+    pub played: usize,
+    pub won: usize,
+    /// Games won in a row, up to the last one
+    pub current_streak: usize,
+    pub max_streak: usize,
+    /// Number of games won on each row, the first row being 1
+    pub distribution: BTreeMap<usize, usize>,
+}
+
+impl PlayerStats {
+    /// Adds a game that ended on `row`, counted from 1
+    pub fn record(&mut self, won: bool, row: usize) {
+        self.played += 1;
+        if won {
+            self.won += 1;
+            self.current_streak += 1;
+            self.max_streak = self.max_streak.max(self.current_streak);
+            *self.distribution.entry(row).or_default() += 1;
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    /// Share of the games played that were won, rounded to a whole percentage
+    pub fn win_percentage(&self) -> usize {
+        if self.played == 0 {
+            return 0;
+        }
+        (self.won * 100 + self.played / 2) / self.played
+    }
+}
+
+/// Statistics of a player shown in a dialog
+pub struct StatsDialog {
+    pub stats: PlayerStats,
+    /// Row of the game just won, highlighted in the distribution
+    pub highlight: Option<usize>,
+}
@@ -0,0 +1,16 @@
+/// How a word fared as the secret of past games
+#[derive(Clone, Copy, Default, Debug)]
+pub struct WordUsage {
+    /// Games that started with the word as their secret
+    pub picked: usize,
+    pub won: usize,
+    pub lost: usize,
+}
+
+impl WordUsage {
+    /// Share of finished games that were won, from 0 to 100
+    pub fn win_rate(&self) -> Option<usize> {
+        let finished = self.won + self.lost;
+        (finished > 0).then(|| self.won * 100 / finished)
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use color_eyre::{Result, eyre::OptionExt};
+
+use crate::{
+    model::{cell::CellState, game_state::GameState},
+    rules::{GameRules, determine_cell_states},
+    service::dictionary::WordService,
+};
+
+/// The secret is not fixed: after every guess the game keeps the largest group of words
+/// that are still consistent with all the feedback given so far
+pub struct Adversarial;
+
+#[async_trait]
+impl GameRules for Adversarial {
+    fn id(&self) -> &'static str {
+        "adversarial"
+    }
+
+    fn name(&self) -> &'static str {
+        "Adversarial"
+    }
+
+    fn description(&self) -> &'static str {
+        "The game changes the secret word to dodge your guesses"
+    }
+
+    fn keeps_secret(&self) -> bool {
+        false
+    }
+
+    fn allows_hints(&self) -> bool {
+        // The secret isn't known until the end, so there is nothing to reveal
+        false
+    }
+
+    fn reveals_letter_counts(&self) -> bool {
+        // Counts would narrow the candidates more than the chosen feedback group does
+        false
+    }
+
+    async fn score_guess(
+        &self,
+        game_state: &mut GameState,
+        guess: &[char],
+        word_service: &dyn WordService,
+    ) -> Result<Vec<CellState>> {
+        let spellings = word_service
+            .get_spellings_by_length(game_state.settings.word_length)
+            .await?;
+
+        // Group the words still consistent with the previous rows by the feedback they'd give
+        let mut buckets: HashMap<Vec<CellState>, Vec<usize>> = HashMap::new();
+        for (i, spelling) in spellings.iter().enumerate() {
+            let consistent = game_state.grid.scored_rows().all(|row| {
+                determine_cell_states(&row.letters(), &spelling.letters) == row.states()
+            });
+
+            if consistent {
+                buckets
+                    .entry(determine_cell_states(guess, &spelling.letters))
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        // Prefer the largest group, then the one revealing the fewest letters
+        let Some((states, indices)) = buckets.into_iter().max_by_key(|(states, indices)| {
+            let revealed = states.iter().filter(|s| **s != CellState::Absent).count();
+            (indices.len(), std::cmp::Reverse(revealed))
+        }) else {
+            let secret: Vec<char> = game_state
+                .language
+                .normalize(&game_state.secret_word.word)
+                .chars()
+                .collect();
+            return Ok(determine_cell_states(guess, &secret));
+        };
+
+        // Only the word kept as the secret needs its full entry
+        let secret = &spellings[indices[0]].word;
+        game_state.secret_word = word_service
+            .get_word(secret)
+            .await?
+            .ok_or_eyre(format!("{secret} is missing from the dictionary"))?;
+        Ok(states)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::rules::GameRules;
+
+/// The original rules: every tile is coloured after each guess
+pub struct Classic;
+
+impl GameRules for Classic {
+    fn id(&self) -> &'static str {
+        "classic"
+    }
+
+    fn name(&self) -> &'static str {
+        "Classic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guess the word, every letter is coloured after each try"
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    model::{cell::CellState, row::Row},
+    rules::GameRules,
+};
+
+/// Only the number of correct and present letters is shown, not which tiles they are
+pub struct CountsOnly;
+
+impl GameRules for CountsOnly {
+    fn id(&self) -> &'static str {
+        "counts-only"
+    }
+
+    fn name(&self) -> &'static str {
+        "Counts only"
+    }
+
+    fn description(&self) -> &'static str {
+        "Each guess only tells how many letters are correct or present"
+    }
+
+    fn reveals_letter_counts(&self) -> bool {
+        // Counts would give away which tiles are confirmed
+        false
+    }
+
+    fn apply_feedback(&self, row: &mut Row, guess: &[char], states: &[CellState]) {
+        row.fill(guess, &vec![CellState::Concealed; states.len()]);
+        row.summary = Some(states.into());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{rules::GameRules, service::dictionary::Word};
+
+/// Clues shorter than this don't say enough about the word
+const MIN_CLUE_LENGTH: usize = 15;
+/// Clues longer than this are usually encyclopedic rather than a definition
+const MAX_CLUE_LENGTH: usize = 400;
+
+/// Definitions that only point to another entry or inflection
+const CROSS_REFERENCES: [&str; 8] = [
+    "See ", "Same as", "Obs.", "imp.", "p. p.", "pl. of", "Pl. of", "3d pers.",
+];
+
+/// A definition of the secret is shown up front, guesses are scored as usual
+pub struct Definition;
+
+impl Definition {
+    /// First sense of the word itself that makes a good clue, with the word masked out
+    fn usable_clue(word: &Word) -> Option<String> {
+        word.definitions
+            .iter()
+            .filter(|definition| definition.term.is_none())
+            .filter(|definition| !definition.labels.iter().any(|label| label == "Obs."))
+            .map(|definition| word.redact(&definition.sense))
+            .find(|clue| {
+                (MIN_CLUE_LENGTH..=MAX_CLUE_LENGTH).contains(&clue.len())
+                    && !CROSS_REFERENCES
+                        .iter()
+                        .any(|reference| clue.starts_with(reference))
+            })
+    }
+}
+
+impl GameRules for Definition {
+    fn id(&self) -> &'static str {
+        "definition"
+    }
+
+    fn name(&self) -> &'static str {
+        "Definition"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guess the word from its dictionary definition"
+    }
+
+    fn accepts_secret(&self, word: &Word) -> bool {
+        Self::usable_clue(word).is_some()
+    }
+
+    fn clue(&self, secret: &Word) -> Option<String> {
+        Self::usable_clue(secret)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    model::{cell::CellState, game_state::GameState},
+    rules::GameRules,
+};
+
+/// Revealed hints must be used in every following guess
+pub struct Hard;
+
+impl GameRules for Hard {
+    fn id(&self) -> &'static str {
+        "hard"
+    }
+
+    fn name(&self) -> &'static str {
+        "Hard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Correct letters must stay in place and present letters must be reused"
+    }
+
+    fn validate_guess(&self, game_state: &GameState, guess: &[char]) -> bool {
+        game_state.grid.scored_rows().all(|row| {
+            let mut remaining = guess.to_vec();
+
+            for (i, cell) in row.cells.iter().enumerate() {
+                if cell.state == CellState::Correct {
+                    if guess.get(i) != cell.letter.as_ref() {
+                        return false;
+                    }
+                    remaining[i] = '\0';
+                }
+            }
+
+            row.cells
+                .iter()
+                .filter(|cell| cell.state == CellState::Present)
+                .all(|cell| {
+                    match remaining
+                        .iter()
+                        .position(|c| Some(c) == cell.letter.as_ref())
+                    {
+                        Some(j) => {
+                            remaining[j] = '\0';
+                            true
+                        }
+                        None => false,
+                    }
+                })
+        })
+    }
+}
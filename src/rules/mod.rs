@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+
+use crate::{
+    model::{cell::CellState, game_state::GameState, row::Row},
+    service::dictionary::{Word, WordService},
+};
+
+pub mod adversarial;
+pub mod classic;
+pub mod counts_only;
+pub mod definition;
+pub mod hard;
+
+/// Scores `guess` against `secret` with the usual correct/present/absent rules.
+///
+/// Duplicate letters are only marked present as many times as they occur in the secret.
+pub fn determine_cell_states(guess: &[char], secret: &[char]) -> Vec<CellState> {
+    let mut secret_chars = secret.to_vec();
+    let mut states = vec![CellState::Absent; guess.len()];
+
+    // Mark correct positions first
+    for (i, (g, s)) in guess.iter().zip(secret_chars.iter_mut()).enumerate() {
+        if g == s {
+            states[i] = CellState::Correct;
+            *s = '\0';
+        }
+    }
+
+    // Mark present letters
+    for (i, g) in guess.iter().enumerate() {
+        if states[i] == CellState::Absent
+            && let Some(j) = secret_chars.iter().position(|s| s == g)
+        {
+            states[i] = CellState::Present;
+            secret_chars[j] = '\0';
+        }
+    }
+    states
+}
+
+/// For every confirmed letter of a scored guess, how many times it occurs in `secret`.
+///
+/// Only duplicate letters are reported, letters that are absent or occur once get `None`.
+pub fn determine_letter_counts(
+    guess: &[char],
+    secret: &[char],
+    states: &[CellState],
+) -> Vec<Option<usize>> {
+    guess
+        .iter()
+        .zip(states.iter())
+        .map(|(g, state)| {
+            let confirmed = matches!(state, CellState::Correct | CellState::Present);
+            let occurrences = secret.iter().filter(|s| *s == g).count();
+            (confirmed && occurrences > 1).then_some(occurrences)
+        })
+        .collect()
+}
+
+/// Rules of a game mode: how guesses are validated, scored and shown
+#[async_trait]
+pub trait GameRules: Send + Sync {
+    /// Identifier used to select the mode and stored in `GameState::mode`
+    fn id(&self) -> &'static str;
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+
+    /// Whether a word can be picked as the secret of a game in this mode
+    fn accepts_secret(&self, _word: &Word) -> bool {
+        true
+    }
+
+    /// Clue shown before the first guess
+    fn clue(&self, _secret: &Word) -> Option<String> {
+        None
+    }
+
+    /// Whether letter counts can be shown on confirmed tiles
+    fn reveals_letter_counts(&self) -> bool {
+        true
+    }
+
+    /// Whether the secret picked for a game stays its secret until the end
+    fn keeps_secret(&self) -> bool {
+        true
+    }
+
+    /// Whether players may request hints, which reveal information about the secret word
+    fn allows_hints(&self) -> bool {
+        true
+    }
+
+    /// Mode-specific checks, run after the guess has been found in the dictionary
+    fn validate_guess(&self, _game_state: &GameState, _guess: &[char]) -> bool {
+        true
+    }
+
+    /// Scores a normalized guess against the secret word. Modes may update the secret while
+    /// doing so.
+    async fn score_guess(
+        &self,
+        game_state: &mut GameState,
+        guess: &[char],
+        _word_service: &dyn WordService,
+    ) -> Result<Vec<CellState>> {
+        let secret: Vec<char> = game_state
+            .language
+            .normalize(&game_state.secret_word.word)
+            .chars()
+            .collect();
+        Ok(determine_cell_states(guess, &secret))
+    }
+
+    /// Writes the scored guess into a row of the grid
+    fn apply_feedback(&self, row: &mut Row, guess: &[char], states: &[CellState]) {
+        row.fill(guess, states);
+    }
+
+    fn is_win(&self, states: &[CellState]) -> bool {
+        states.iter().all(|state| *state == CellState::Correct)
+    }
+}
+
+/// Game modes available to new games, looked up by their id
+#[derive(Clone)]
+pub struct RulesRegistry {
+    modes: Vec<Arc<dyn GameRules>>,
+}
+
+impl RulesRegistry {
+    pub fn empty() -> Self {
+        Self { modes: Vec::new() }
+    }
+
+    /// Registers a mode, replacing any mode with the same id
+    pub fn register(&mut self, rules: Arc<dyn GameRules>) {
+        self.modes.retain(|mode| mode.id() != rules.id());
+        self.modes.push(rules);
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn GameRules>> {
+        self.modes.iter().find(|mode| mode.id() == id).cloned()
+    }
+
+    pub fn modes(&self) -> impl Iterator<Item = &Arc<dyn GameRules>> {
+        self.modes.iter()
+    }
+}
+
+impl Default for RulesRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(classic::Classic));
+        registry.register(Arc::new(hard::Hard));
+        registry.register(Arc::new(adversarial::Adversarial));
+        registry.register(Arc::new(counts_only::CountsOnly));
+        registry.register(Arc::new(definition::Definition));
+        registry
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use serde::Deserialize;
+
+use crate::service::{
+    commonness::Difficulty,
+    dictionary::{Spelling, SpellingCache, Word, WordService, tokenize},
+    language::Language,
+};
+
+/// Blocklist file, grouping the blocked words in categories
+///
+/// ```json
+/// {
+///     "categories": {
+///         "slurs": {"words": "slurs.txt"},
+///         "vulgar": {"words": ["..."], "labels": ["Low", "Vulgar"]}
+///     },
+///     "blocked": ["slurs", "vulgar"],
+///     "accept_guesses": true
+/// }
+/// ```
+#[derive(Deserialize)]
+struct BlocklistConfig {
+    categories: HashMap<String, Category>,
+    /// Categories to block, all of them when missing
+    blocked: Option<Vec<String>>,
+    #[serde(default)]
+    accept_guesses: bool,
+}
+
+#[derive(Deserialize)]
+struct Category {
+    #[serde(default)]
+    words: WordSource,
+    /// Usage labels of the dictionary, a word is blocked if one of its definitions has one
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WordSource {
+    Inline(Vec<String>),
+    /// File with one word per line, relative to the blocklist file
+    File(PathBuf),
+}
+
+impl Default for WordSource {
+    fn default() -> Self {
+        Self::Inline(Vec::new())
+    }
+}
+
+/// Words that must not be picked as secret words or suggested to players
+#[derive(Default)]
+pub struct Blocklist {
+    words: Vec<String>,
+    /// Lowercase usage labels, without their final period
+    labels: HashSet<String>,
+    /// Whether blocked words are still valid guesses
+    pub accept_guesses: bool,
+}
+
+impl Blocklist {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Can't read blocklist {}", path.display()))?;
+        let config: BlocklistConfig = serde_json::from_str(&text)
+            .wrap_err_with(|| format!("Invalid blocklist {}", path.display()))?;
+
+        let blocked = match config.blocked {
+            Some(blocked) => blocked,
+            None => config.categories.keys().cloned().collect(),
+        };
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let mut blocklist = Self {
+            accept_guesses: config.accept_guesses,
+            ..Default::default()
+        };
+
+        for name in blocked {
+            let Some(category) = config.categories.get(&name) else {
+                bail!("Unknown category {name} in blocklist {}", path.display());
+            };
+            match &category.words {
+                WordSource::Inline(words) => blocklist.words.extend(words.iter().cloned()),
+                WordSource::File(source) => {
+                    let file = directory.join(source);
+                    let text = fs::read_to_string(&file)
+                        .wrap_err_with(|| format!("Can't read {}", file.display()))?;
+                    blocklist.words.extend(
+                        text.lines()
+                            .map(str::trim)
+                            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                            .map(String::from),
+                    );
+                }
+            }
+            blocklist.labels.extend(
+                category
+                    .labels
+                    .iter()
+                    .map(|label| label.trim_end_matches('.').to_lowercase()),
+            );
+        }
+        Ok(blocklist)
+    }
+
+    /// Whether a usage label such as "Colloq. or Low" names a blocked label
+    fn blocks_label(&self, label: &str) -> bool {
+        tokenize(label).any(|token| self.labels.contains(&token.to_lowercase()))
+    }
+}
+
+/// The words of a dictionary that aren't blocked, used as the pool of secret words
+pub struct BlockedWordService {
+    inner: Arc<dyn WordService>,
+    blocklist: Arc<Blocklist>,
+    /// Blocked words in normalized form
+    words: HashSet<String>,
+    spellings: SpellingCache,
+}
+
+impl BlockedWordService {
+    pub fn new(inner: Arc<dyn WordService>, blocklist: Arc<Blocklist>) -> Self {
+        let language = inner.language();
+        let words = blocklist
+            .words
+            .iter()
+            .map(|word| language.normalize(&language.uppercase(word)))
+            .collect();
+        Self {
+            inner,
+            blocklist,
+            words,
+            spellings: SpellingCache::default(),
+        }
+    }
+
+    pub fn blocks(&self, word: &Word) -> bool {
+        self.words.contains(&self.language().normalize(&word.word))
+            || word
+                .definitions
+                .iter()
+                .flat_map(|definition| &definition.labels)
+                .any(|label| self.blocklist.blocks_label(label))
+    }
+
+    fn allowed(&self, words: Vec<Word>) -> Vec<Word> {
+        words
+            .into_iter()
+            .filter(|word| !self.blocks(word))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl WordService for BlockedWordService {
+    fn language(&self) -> &Language {
+        self.inner.language()
+    }
+
+    async fn get_random_word(&self, length: usize) -> Result<Word> {
+        self.get_random_word_where(length, Default::default(), &|_| true)
+            .await
+    }
+
+    async fn get_random_word_where(
+        &self,
+        length: usize,
+        difficulty: Difficulty,
+        filter: &(dyn for<'w> Fn(&'w Word) -> bool + Sync),
+    ) -> Result<Word> {
+        self.inner
+            .get_random_word_where(length, difficulty, &|word| {
+                !self.blocks(word) && filter(word)
+            })
+            .await
+    }
+
+    async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
+        Ok(self.allowed(self.inner.get_words_by_length(length).await?))
+    }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        let source = self.inner.get_spellings_by_length(length).await?;
+        // Labels are only known from the full entries, read once per list
+        let derive = async {
+            let language = self.language();
+            Ok(self
+                .get_words_by_length(length)
+                .await?
+                .iter()
+                .map(|word| Spelling::new(&word.word, language))
+                .collect())
+        };
+        self.spellings.get_or_derive(length, source, derive).await
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        Ok(self.allowed(self.inner.get_words().await?))
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
+        Ok(self
+            .inner
+            .get_word(word)
+            .await?
+            .filter(|word| !self.blocks(word)))
+    }
+
+    async fn validate_word(&self, word: &str) -> Result<bool> {
+        Ok(self.get_word(word).await?.is_some())
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::HashMap, fmt::Display, fs, path::Path};
+
+use clap::ValueEnum;
+use color_eyre::{Result, eyre::Context};
+use serde::Deserialize;
+
+use crate::service::dictionary::Word;
+
+/// How common the secret words are
+#[derive(ValueEnum, Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Only the most common words, the more common the likelier
+    Easy,
+    /// Any word found in the frequency list
+    #[default]
+    Normal,
+    /// Any dictionary word, including archaic ones
+    Rare,
+}
+
+impl Difficulty {
+    /// Lowest commonness a secret word can have, `None` if words missing from the list are allowed
+    fn min_commonness(self) -> Option<f64> {
+        match self {
+            Self::Easy => Some(0.5),
+            Self::Normal => Some(0.0),
+            Self::Rare => None,
+        }
+    }
+
+    /// Whether common words are picked more often
+    fn is_weighted(self) -> bool {
+        self == Self::Easy
+    }
+
+    pub fn accepts(self, word: &Word) -> bool {
+        match (self.min_commonness(), word.commonness) {
+            (None, _) => true,
+            (Some(min), Some(commonness)) => commonness >= min,
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Relative chance of `word` being picked among the accepted words
+    pub fn weight(self, word: &Word) -> f64 {
+        if self.is_weighted() {
+            // Keeps the least common accepted words possible
+            word.commonness.unwrap_or(0.0) + 0.01
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Rare => "rare",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Word counts from a frequency corpus
+///
+/// Either plain text with one word per line, most common first, optionally followed by its
+/// count (`the 23135851162`), or CSV with `word,count` lines and an optional header.
+pub struct FrequencyList {
+    counts: HashMap<String, u64>,
+}
+
+impl FrequencyList {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Can't read frequency list {}", path.display()))?;
+        Ok(Self::parse(&text))
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        for (rank, line) in lines.iter().enumerate() {
+            let mut fields = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|field| !field.is_empty());
+            let Some(word) = fields.next() else {
+                continue;
+            };
+            let count = match fields.next() {
+                Some(count) => match count.parse::<u64>() {
+                    Ok(count) => count,
+                    // A CSV header such as "word,count"
+                    Err(_) => continue,
+                },
+                // Without counts the order of the lines gives the frequency
+                None => (lines.len() - rank) as u64,
+            };
+
+            // Lists are often split by part of speech, the same word can appear more than once
+            *counts.entry(word.to_uppercase()).or_default() += count;
+        }
+
+        Self { counts }
+    }
+
+    /// Sets the commonness of the words found in the list, from 0 (the rarest of them) to 1
+    /// (the most common), and clears it for the others
+    pub fn apply(&self, words: &mut [Word]) {
+        let mut listed: Vec<(usize, u64)> = words
+            .iter()
+            .enumerate()
+            .filter_map(|(i, w)| self.counts.get(&w.word).map(|&count| (i, count)))
+            .collect();
+        listed.sort_unstable_by_key(|&(_, count)| count);
+
+        for word in words.iter_mut() {
+            word.commonness = None;
+        }
+        let last = listed.len().saturating_sub(1).max(1) as f64;
+        for (rank, (i, _)) in listed.into_iter().enumerate() {
+            words[i].commonness = Some(rank as f64 / last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word: &str, commonness: Option<f64>) -> Word {
+        Word {
+            word: word.to_string(),
+            meanings: Vec::new(),
+            frequency: 0,
+            commonness,
+            definitions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn difficulties_accept_words_by_commonness() {
+        let common = word("THE", Some(0.9));
+        let uncommon = word("OTTER", Some(0.2));
+        let unlisted = word("YCLEPT", None);
+
+        assert!(Difficulty::Easy.accepts(&common));
+        assert!(!Difficulty::Easy.accepts(&uncommon));
+        assert!(!Difficulty::Easy.accepts(&unlisted));
+
+        assert!(Difficulty::Normal.accepts(&common));
+        assert!(Difficulty::Normal.accepts(&uncommon));
+        assert!(!Difficulty::Normal.accepts(&unlisted));
+
+        assert!(Difficulty::Rare.accepts(&common));
+        assert!(Difficulty::Rare.accepts(&uncommon));
+        assert!(Difficulty::Rare.accepts(&unlisted));
+    }
+
+    #[test]
+    fn only_easy_favours_common_words() {
+        let common = word("THE", Some(0.9));
+        let rarest = word("OTTER", Some(0.0));
+
+        assert!(Difficulty::Easy.weight(&common) > Difficulty::Easy.weight(&rarest));
+        assert!(Difficulty::Easy.weight(&rarest) > 0.0);
+        for difficulty in [Difficulty::Normal, Difficulty::Rare] {
+            assert_eq!(difficulty.weight(&common), difficulty.weight(&rarest));
+        }
+    }
+
+    #[test]
+    fn ranks_listed_words_and_clears_the_others() {
+        let list = FrequencyList::parse("word,count\nthe,500\nof,300\nthe,100\ncrate,10\n");
+        let mut words = vec![
+            word("CRATE", None),
+            word("OTTER", Some(0.5)),
+            word("THE", None),
+            word("OF", None),
+        ];
+        list.apply(&mut words);
+
+        let commonness: Vec<Option<f64>> = words.iter().map(|w| w.commonness).collect();
+        assert_eq!(commonness, [Some(0.0), None, Some(1.0), Some(0.5)]);
+    }
+
+    #[test]
+    fn plain_lists_are_ranked_by_line() {
+        let list = FrequencyList::parse("# Most common first\nthe\nof\n\ncrate\n");
+        let mut words = vec![word("CRATE", None), word("OF", None), word("THE", None)];
+        list.apply(&mut words);
+
+        let commonness: Vec<Option<f64>> = words.iter().map(|w| w.commonness).collect();
+        assert_eq!(commonness, [Some(0.0), Some(0.5), Some(1.0)]);
+    }
+}
@@ -0,0 +1,499 @@
+use std::{collections::HashSet, fmt::Display, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::service::language::{ENGLISH, Language};
+
+/// Short words that the source data often glues to the next or previous word ("alsothe")
+const GLUE_WORDS: [&str; 21] = [
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "is", "it", "of", "on", "or", "that",
+    "the", "to", "which", "with", "its", "not",
+];
+
+/// Glue words that never end a longer word, so "fromthe" can be split while "into" stays whole
+const LEADING_GLUE_WORDS: [&str; 3] = ["the", "that", "which"];
+
+/// Endings of inflected forms, which the dictionary only lists under their stem
+const INFLECTIONS: [&str; 5] = ["s", "es", "ed", "ing", "ly"];
+
+/// Both halves of a compound must be at least this long to be split apart
+const MIN_COMPOUND_PART: usize = 6;
+
+/// Sub-entries this short are part of speech markers, e.g. "v. t."
+const MAX_MARKER_LENGTH: usize = 5;
+
+/// Summaries longer than this are cut at a word boundary
+const MAX_SUMMARY_LENGTH: usize = 160;
+
+/// Pronunciation of the next entry leaking into this one, e.g. `Alt"horn`
+static LEAKED_ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\s\S*\p{L}"\p{L}"#).expect("Invalid regex"));
+static ETYMOLOGY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Etym:\s*\[[^\]]*\]").expect("Invalid regex"));
+/// Punctuation directly followed by a word, where a line break was dropped
+static MISSING_SPACE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([,;:])(\p{L})").expect("Invalid regex"));
+static MISSING_SENTENCE_SPACE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\p{Ll}{2})\.(\p{Lu})").expect("Invalid regex"));
+/// A sub-entry that is not introduced by "--", e.g. `... horses. Bay cat (Zoöl.), a wild cat`
+static INLINE_SUB_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\.\s+(\p{Lu}[\p{Ll}']+(?:\s[\p{L}']+){0,3}\s\(\p{Lu}[^)]{0,30}\.\),)")
+        .expect("Invalid regex")
+});
+static LABEL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]{1,40})\]").expect("Invalid regex"));
+static DOMAIN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*\((\p{Lu}\p{L}*\.(?:\s?\p{Lu}?\p{L}*\.)*)\)").expect("Invalid regex")
+});
+static QUOTATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"\s]+\s[^"]{1,300})""#).expect("Invalid regex"));
+static AS_EXAMPLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r";?\s*\bas,\s*([^.]+)").expect("Invalid regex"));
+/// Author of a quotation at the end of a sense, e.g. `Milton.` or `W. Smith.`
+static CITATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:\p{Lu}\.\s)?\p{Lu}[\p{L}']+(?:\s\p{Lu}[\p{L}']+)?\.$").expect("Invalid regex")
+});
+/// Initials and titles left over from a citation, e.g. the `S. W.` of `S. W. Williams.`
+static TRAILING_INITIALS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:\s\p{Lu}\p{Ll}?\.)+$").expect("Invalid regex"));
+/// A sub-entry such as `Bay cat (Zoöl.), a wild cat...`
+static SUB_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\p{Lu}[^,.;(]{0,50}?)\s*(?:\(([^)]{1,30})\))?,\s+(.+)$").expect("Invalid regex")
+});
+/// A cross-reference sub-entry such as `Brussels point. See Point lace.`
+static SUB_ENTRY_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\p{Lu}[^.;]{0,50})\.\s+(See .+)$").expect("Invalid regex"));
+static WHITESPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").expect("Invalid regex"));
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+}
+
+impl PartOfSpeech {
+    /// Guesses the part of speech from the usual wording of Webster definitions
+    fn infer(sense: &str) -> Option<Self> {
+        const VERB: [&str; 4] = ["To ", "imp. ", "p. p.", "p. pr."];
+        const ADVERB: [&str; 2] = ["In a ", "In an "];
+        const NOUN: [&str; 9] = [
+            "A ",
+            "An ",
+            "The ",
+            "One who",
+            "One that",
+            "That which",
+            "Any ",
+            "pl. of",
+            "Pl. of",
+        ];
+        const ADJECTIVE: [&str; 9] = [
+            "Of or pertaining",
+            "Pertaining",
+            "Having",
+            "Relating",
+            "Full of",
+            "Like ",
+            "Resembling",
+            "Of ",
+            "Not ",
+        ];
+
+        let starts = |prefixes: &[&str]| prefixes.iter().any(|p| sense.starts_with(p));
+        if starts(&VERB) {
+            Some(Self::Verb)
+        } else if starts(&ADVERB) || sense.contains(" manner") {
+            Some(Self::Adverb)
+        } else if starts(&NOUN) {
+            Some(Self::Noun)
+        } else if starts(&ADJECTIVE) {
+            Some(Self::Adjective)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for PartOfSpeech {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Noun => "noun",
+            Self::Verb => "verb",
+            Self::Adjective => "adjective",
+            Self::Adverb => "adverb",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A cleaned-up sense of a dictionary entry
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Definition {
+    /// Compound or phrase this sense is for, e.g. "Bay cat" in the entry of "bay"
+    pub term: Option<String>,
+    /// Field of knowledge, e.g. "Zoöl."
+    pub domain: Option<String>,
+    /// Usage labels such as "Obs." or "Colloq."
+    pub labels: Vec<String>,
+    /// Inferred from the wording of the sense
+    pub part_of_speech: Option<PartOfSpeech>,
+    pub sense: String,
+    pub examples: Vec<String>,
+}
+
+impl Definition {
+    /// First sentence of the sense, cut to a readable length
+    pub fn summary(&self) -> String {
+        // A sentence ends at a period followed by a capital, abbreviations like "esp." don't
+        let end = self
+            .sense
+            .match_indices(". ")
+            .map(|(i, _)| i)
+            .find(|&i| self.sense[i + 2..].starts_with(char::is_uppercase));
+        let first = match end {
+            Some(end) => &self.sense[..=end],
+            None => &self.sense,
+        };
+        if first.chars().count() <= MAX_SUMMARY_LENGTH {
+            return first.to_string();
+        }
+
+        let mut summary: String = first.chars().take(MAX_SUMMARY_LENGTH).collect();
+        if let Some(end) = summary.rfind(' ') {
+            summary.truncate(end);
+        }
+        summary.push('…');
+        summary
+    }
+}
+
+/// Turns the raw Webster text of an entry into structured definitions
+pub struct Normalizer<'a> {
+    /// Lowercase words of the dictionary, used to split words that were glued together
+    words: &'a HashSet<String>,
+    /// Whether to split glued words, whose rules only hold for English
+    split_glued_words: bool,
+}
+
+impl<'a> Normalizer<'a> {
+    /// Normalizer of the definitions of `language`, the words of its dictionary being `words`
+    pub fn new(words: &'a HashSet<String>, language: &Language) -> Self {
+        Self {
+            words,
+            split_glued_words: language.id == ENGLISH,
+        }
+    }
+
+    pub fn normalize(&self, raw: &str) -> Vec<Definition> {
+        let mut text = raw.to_string();
+        if let Some(leak) = LEAKED_ENTRY.find(&text) {
+            text.truncate(leak.start());
+        }
+        let text = ETYMOLOGY.replace_all(&text, "");
+        let text = MISSING_SPACE.replace_all(&text, "$1 $2");
+        let text = MISSING_SENTENCE_SPACE.replace_all(&text, "$1. $2");
+        let text = if self.split_glued_words {
+            self.split_glued_words(&text)
+        } else {
+            text.into_owned()
+        };
+        let text = INLINE_SUB_ENTRY.replace_all(&text, ".--$1");
+
+        let mut definitions: Vec<Definition> = Vec::new();
+        for segment in text.split("--").map(str::trim).filter(|s| !s.is_empty()) {
+            let starts_lowercase = segment.chars().next().is_some_and(char::is_lowercase);
+            match definitions.last_mut() {
+                // Bare part of speech markers such as "-- n." add nothing
+                Some(_) if starts_lowercase && segment.len() <= MAX_MARKER_LENGTH => {}
+                // Usage notes such as "-- said of sails" belong to the previous sense
+                Some(previous) if starts_lowercase => {
+                    let note = Self::clean(segment);
+                    previous.sense = format!("{}; {note}", previous.sense.trim_end_matches('.'));
+                }
+                Some(_) => definitions.push(Self::sub_entry(segment)),
+                None => definitions.push(Self::definition(segment)),
+            }
+        }
+
+        definitions.retain(|d| !d.sense.is_empty() || d.term.is_some());
+        definitions
+    }
+
+    fn sub_entry(segment: &str) -> Definition {
+        if let Some(captures) = SUB_ENTRY_REFERENCE.captures(segment) {
+            return Definition {
+                term: Some(captures[1].trim().to_string()),
+                sense: captures[2].trim().to_string(),
+                ..Definition::default()
+            };
+        }
+
+        match SUB_ENTRY.captures(segment) {
+            Some(captures) => {
+                let mut definition = Self::definition(&captures[3]);
+                definition.term = Some(captures[1].trim().to_string());
+                if let Some(domain) = captures.get(2) {
+                    definition.domain = Some(domain.as_str().to_string());
+                }
+                definition
+            }
+            None => Self::definition(segment),
+        }
+    }
+
+    fn definition(text: &str) -> Definition {
+        let labels = LABEL
+            .captures_iter(text)
+            .map(|c| c[1].trim().to_string())
+            .collect();
+        let text = LABEL.replace_all(text, "");
+
+        let domain = DOMAIN.captures(&text).map(|c| c[1].to_string());
+        let text = DOMAIN.replace(&text, "");
+
+        let mut examples: Vec<String> = QUOTATION
+            .captures_iter(&text)
+            .map(|c| c[1].trim_end_matches([',', '.', ' ']).trim().to_string())
+            .collect();
+        let text = QUOTATION.replace_all(&text, "");
+
+        examples.extend(
+            AS_EXAMPLE
+                .captures_iter(&text)
+                .flat_map(|c| {
+                    c[1].split(';')
+                        .map(|e| e.trim().to_string())
+                        .collect::<Vec<_>>()
+                })
+                .filter(|e| !e.is_empty()),
+        );
+        let text = AS_EXAMPLE.replace_all(&text, "");
+
+        let sense = Self::clean(&Self::strip_citations(&text, &mut examples));
+        Definition {
+            term: None,
+            domain,
+            labels,
+            part_of_speech: PartOfSpeech::infer(&sense),
+            sense,
+            examples,
+        }
+    }
+
+    /// Removes the authors cited at the end of the sense, moving the passages they are cited
+    /// for to `examples`
+    fn strip_citations(text: &str, examples: &mut Vec<String>) -> String {
+        let mut text = text.trim().to_string();
+        let mut quotations = Vec::new();
+        while let Some(start) = text.trim_end_matches('.').rfind(". ") {
+            let tail = text[start + 2..].trim();
+            if tail.starts_with("See ") || !CITATION.is_match(tail) {
+                break;
+            }
+            text.truncate(start + 1);
+            let end = TRAILING_INITIALS
+                .find(&text)
+                .map_or(text.len(), |m| m.start());
+            text.truncate(end);
+
+            // The sentence before a citation is a quotation, unless it is the sense itself
+            if let Some(quotation) = Self::last_sentence_start(&text) {
+                quotations.push(text[quotation..].trim().to_string());
+                text.truncate(quotation);
+                text.truncate(text.trim_end().len());
+            }
+        }
+
+        examples.extend(quotations.into_iter().rev());
+        text
+    }
+
+    fn last_sentence_start(text: &str) -> Option<usize> {
+        text.trim_end_matches('.')
+            .match_indices(". ")
+            .map(|(i, _)| i + 2)
+            .filter(|&i| text[i..].starts_with(char::is_uppercase))
+            .last()
+    }
+
+    fn clean(text: &str) -> String {
+        let text = WHITESPACE.replace_all(text.trim(), " ");
+        let text = text.trim_matches(|c: char| c == ';' || c == ',' || c.is_whitespace());
+        if text.is_empty() || text.ends_with(['.', '!', '?', ')']) {
+            text.to_string()
+        } else {
+            format!("{text}.")
+        }
+    }
+
+    /// Puts back the spaces lost at line breaks, e.g. "alsothe" or "EastIndies"
+    fn split_glued_words(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut token = String::new();
+
+        for c in text.chars().chain(std::iter::once(' ')) {
+            if c.is_alphabetic() {
+                token.push(c);
+                continue;
+            }
+            if !token.is_empty() {
+                result.push_str(&self.split_token(&token));
+                token.clear();
+            }
+            result.push(c);
+        }
+
+        result.pop();
+        result
+    }
+
+    fn split_token(&self, token: &str) -> String {
+        let lowercase = token.to_lowercase();
+        if token.chars().count() < 4 || self.words.contains(&lowercase) {
+            return token.to_string();
+        }
+
+        // "EastIndies": a capital letter in the middle of a word
+        let chars: Vec<char> = token.chars().collect();
+        if let Some(i) =
+            (1..chars.len()).find(|&i| chars[i].is_uppercase() && chars[i - 1].is_lowercase())
+        {
+            let (left, right): (String, String) =
+                (chars[..i].iter().collect(), chars[i..].iter().collect());
+            return format!("{left} {right}");
+        }
+
+        // Names such as authors are not in the dictionary but should stay whole
+        if self.is_inflection(&lowercase) || chars[0].is_uppercase() {
+            return token.to_string();
+        }
+
+        // "alsothe": a short common word glued to a dictionary word
+        // "variousspecies": two longer dictionary words
+        for i in 1..chars.len() {
+            let (left, right): (String, String) =
+                (chars[..i].iter().collect(), chars[i..].iter().collect());
+            let (left_lower, right_lower) = (left.to_lowercase(), right.to_lowercase());
+            if GLUE_WORDS.contains(&left_lower.as_str())
+                && GLUE_WORDS.contains(&right_lower.as_str())
+                && !LEADING_GLUE_WORDS.contains(&right_lower.as_str())
+            {
+                continue;
+            }
+            let glued = (GLUE_WORDS.contains(&left_lower.as_str()) && self.is_word(&right_lower))
+                || (GLUE_WORDS.contains(&right_lower.as_str())
+                    && left_lower.chars().count() >= 3
+                    && self.words.contains(&left_lower))
+                || (i >= MIN_COMPOUND_PART
+                    && chars.len() - i >= MIN_COMPOUND_PART
+                    && self.is_word(&left_lower)
+                    && self.is_word(&right_lower));
+            if glued {
+                return format!("{left} {right}");
+            }
+        }
+
+        token.to_string()
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        word.chars().count() >= 3 && (self.words.contains(word) || self.is_inflection(word))
+    }
+
+    fn is_inflection(&self, word: &str) -> bool {
+        INFLECTIONS.iter().any(|suffix| {
+            word.strip_suffix(suffix).is_some_and(|stem| {
+                self.words.contains(stem) || self.words.contains(&format!("{stem}e"))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn glued_words_are_split() {
+        let words = words(&["also", "from", "the", "horse", "letter"]);
+        let normalizer = Normalizer::new(&words, &Language::english());
+
+        assert_eq!(
+            normalizer.split_glued_words("A horse, alsothe letter"),
+            "A horse, also the letter"
+        );
+        assert_eq!(
+            normalizer.split_glued_words("made fromthe letter"),
+            "made from the letter"
+        );
+        // Known words and names stay whole
+        assert_eq!(
+            normalizer.split_glued_words("horses into Aleph"),
+            "horses into Aleph"
+        );
+    }
+
+    #[test]
+    fn glued_words_are_only_split_in_english() {
+        let words = words(&["also", "from", "the"]);
+        let italian = Language {
+            id: "it".to_string(),
+            ..Language::english()
+        };
+        let definitions = Normalizer::new(&words, &italian).normalize("Bevanda fromthe semi");
+
+        assert_eq!(definitions[0].sense, "Bevanda fromthe semi.");
+    }
+
+    #[test]
+    fn etymology_is_dropped() {
+        let definitions = Normalizer::new(&HashSet::new(), &Language::english())
+            .normalize("Etym: [AS. hors.] A hoofed quadruped, used for riding.");
+
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].sense, "A hoofed quadruped, used for riding.");
+        assert_eq!(definitions[0].part_of_speech, Some(PartOfSpeech::Noun));
+    }
+
+    #[test]
+    fn inline_sub_entries_are_split_off() {
+        let definitions = Normalizer::new(&HashSet::new(), &Language::english())
+            .normalize("Reddish brown; as, a bay horse. Bay cat (Zoöl.), a wild cat of Borneo.");
+
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].sense, "Reddish brown.");
+        assert_eq!(definitions[0].examples, ["a bay horse"]);
+        assert_eq!(definitions[1].term.as_deref(), Some("Bay cat"));
+        assert_eq!(definitions[1].domain.as_deref(), Some("Zoöl."));
+        assert_eq!(definitions[1].sense, "a wild cat of Borneo.");
+    }
+
+    #[test]
+    fn summary_is_cut_on_a_character_boundary() {
+        let definition = Definition {
+            sense: "Zoölogie ".repeat(40),
+            ..Definition::default()
+        };
+
+        let summary = definition.summary();
+        assert!(summary.ends_with('…'));
+        assert!(summary.chars().count() <= MAX_SUMMARY_LENGTH + 1);
+        assert!(summary.starts_with("Zoölogie Zoölogie"));
+    }
+
+    #[test]
+    fn summary_keeps_the_first_sentence() {
+        let definition = Definition {
+            sense: "A café, esp. a small one. It serves coffee.".to_string(),
+            ..Definition::default()
+        };
+
+        assert_eq!(definition.summary(), "A café, esp. a small one.");
+    }
+}
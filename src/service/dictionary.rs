@@ -1,10 +1,19 @@
 use async_trait::async_trait;
 use color_eyre::Result;
-use color_eyre::eyre::OptionExt;
+use color_eyre::eyre::{OptionExt, eyre};
 use rand::seq::IndexedRandom;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+use crate::service::commonness::{Difficulty, FrequencyList};
+use crate::service::definition::{Definition, Normalizer};
+use crate::service::language::Language;
+use crate::service::search::{WordPage, WordQuery};
+
+/// Shorter words in definitions are mostly articles and prepositions, not worth linking
+const MIN_RELATED_LENGTH: usize = 4;
 
 #[derive(Deserialize, Clone)]
 pub struct WordData {
@@ -30,8 +39,11 @@ where
         .map(|(word, meanings)| {
             let meanings: Vec<String> = meanings.split("--").map(String::from).collect();
             Word {
-                word: word.to_string().to_ascii_uppercase(),
+                word: word.to_string(),
                 meanings,
+                frequency: 0,
+                commonness: None,
+                definitions: Vec::new(),
             }
         })
         .collect::<Vec<Word>>())
@@ -41,6 +53,72 @@ where
 pub struct Word {
     pub word: String,
     pub meanings: Vec<String>,
+    /// How many times the word is used in the definitions of the dictionary
+    #[serde(default)]
+    pub frequency: usize,
+    /// Rank in the loaded frequency list, from 0 (rarest) to 1 (most common), if listed
+    #[serde(default)]
+    pub commonness: Option<f64>,
+    /// Cleaned-up version of `meanings`
+    #[serde(skip)]
+    pub definitions: Vec<Definition>,
+}
+
+/// Splits text into its words
+pub fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|token| !token.is_empty())
+}
+
+/// Sets the frequency of every word from its uses in the definitions of all the words
+pub fn count_frequencies(words: &mut [Word]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for meaning in words.iter().flat_map(|w| &w.meanings) {
+        for token in tokenize(meaning) {
+            *counts.entry(token.to_uppercase()).or_default() += 1;
+        }
+    }
+
+    for word in words.iter_mut() {
+        word.frequency = counts.get(&word.word).copied().unwrap_or(0);
+    }
+}
+
+/// Builds the structured definitions of every word from its raw meanings
+pub fn normalize_definitions(words: &mut [Word], language: &Language) {
+    let known: HashSet<String> = words.iter().map(|w| w.word.to_lowercase()).collect();
+    let normalizer = Normalizer::new(&known, language);
+    for word in words.iter_mut() {
+        word.definitions = normalizer.normalize(&word.meanings.join("--"));
+    }
+}
+
+impl Word {
+    /// Masks every occurrence of the word in `text`, ignoring case
+    pub fn redact(&self, text: &str) -> String {
+        let same_letter = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+        let needle: Vec<char> = self.word.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+
+        let mut redacted = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < text.len() {
+            let matches = text.len() - i >= needle.len()
+                && !needle.is_empty()
+                && text[i..i + needle.len()]
+                    .iter()
+                    .zip(&needle)
+                    .all(|(&a, &b)| same_letter(a, b));
+            if matches {
+                redacted.extend(std::iter::repeat_n('_', needle.len()));
+                i += needle.len();
+            } else {
+                redacted.push(text[i]);
+                i += 1;
+            }
+        }
+        redacted
+    }
 }
 
 impl Display for Word {
@@ -49,30 +127,191 @@ impl Display for Word {
     }
 }
 
+/// A word as it is spelled, with the letters guesses are matched against
+#[derive(Clone, Debug)]
+pub struct Spelling {
+    pub word: String,
+    /// Letters of the normalized spelling, see `Language::normalize`
+    pub letters: Vec<char>,
+}
+
+impl Spelling {
+    pub fn new(word: &str, language: &Language) -> Self {
+        Self {
+            word: word.to_string(),
+            letters: language.normalize(word).chars().collect(),
+        }
+    }
+}
+
+/// A list of spellings and the one it was derived from
+type DerivedSpellings = (Arc<[Spelling]>, Arc<[Spelling]>);
+
+/// Spellings derived from those of another service, kept until that service returns a
+/// different list, e.g. after a reload
+#[derive(Default)]
+pub struct SpellingCache {
+    /// By word length, the list of the other service and the one derived from it
+    lists: Mutex<HashMap<usize, DerivedSpellings>>,
+}
+
+impl SpellingCache {
+    /// The list `derive` makes of `source`, computed again only when `source` changes
+    pub async fn get_or_derive(
+        &self,
+        length: usize,
+        source: Arc<[Spelling]>,
+        derive: impl Future<Output = Result<Vec<Spelling>>> + Send,
+    ) -> Result<Arc<[Spelling]>> {
+        if let Some((derived_from, list)) = self
+            .lists
+            .lock()
+            .expect("Spellings lock poisoned")
+            .get(&length)
+            && Arc::ptr_eq(derived_from, &source)
+        {
+            return Ok(list.clone());
+        }
+
+        let list: Arc<[Spelling]> = derive.await?.into();
+        self.lists
+            .lock()
+            .expect("Spellings lock poisoned")
+            .insert(length, (source, list.clone()));
+        Ok(list)
+    }
+}
+
 #[async_trait]
 pub trait WordService: Send + Sync {
+    /// Language of the words, which decides how guesses are uppercased
+    fn language(&self) -> &Language;
     async fn get_random_word(&self, length: usize) -> Result<Word>;
+    /// Picks a random word of the given length among the ones accepted by `filter`,
+    /// restricted and weighted by commonness according to `difficulty`
+    async fn get_random_word_where(
+        &self,
+        length: usize,
+        difficulty: Difficulty,
+        filter: &(dyn for<'w> Fn(&'w Word) -> bool + Sync),
+    ) -> Result<Word> {
+        let words = self.get_words_by_length(length).await?;
+        let mut candidates: Vec<&Word> = words.iter().filter(|w| filter(w)).collect();
+
+        // Without a frequency list every word is as common as the others. Decided on all the
+        // words, as `filter` may only leave unlisted ones, which the difficulty must still drop
+        if words.iter().any(|w| w.commonness.is_some()) {
+            candidates.retain(|w| difficulty.accepts(w));
+        }
+
+        let word = candidates
+            .choose_weighted(&mut rand::rng(), |w| difficulty.weight(w))
+            .map_err(|_| eyre!("No word available for length {length}"))?;
+
+        Ok((*word).clone())
+    }
     async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>>;
+    /// Spellings of the words of `length` letters, without the rest of their entries. They
+    /// are kept by the services, so this is cheap enough to call on every guess.
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>>;
+    async fn get_words(&self) -> Result<Vec<Word>>;
+    async fn get_word(&self, word: &str) -> Result<Option<Word>>;
     async fn validate_word(&self, word: &str) -> Result<bool>;
+
+    /// Other dictionary words used in the definitions of `word`, in order of appearance
+    async fn related_words(&self, word: &Word) -> Result<Vec<String>> {
+        let mut related: Vec<String> = Vec::new();
+        let senses = word.definitions.iter().map(|d| &d.sense);
+        for token in senses.flat_map(|s| tokenize(s)) {
+            let token = token.to_uppercase();
+            if token.chars().count() >= MIN_RELATED_LENGTH
+                && token != word.word
+                && !related.contains(&token)
+                && self.validate_word(&token).await?
+            {
+                related.push(token);
+            }
+        }
+        Ok(related)
+    }
+
+    /// Finds the words matching a query, most frequent first
+    async fn search(&self, query: &WordQuery) -> Result<WordPage> {
+        let words = match query.length() {
+            Some(length) => self.get_words_by_length(length).await?,
+            None => self.get_words().await?,
+        };
+        query.paginate(words)
+    }
 }
 
 pub struct DictionaryService {
+    language: Language,
     dictionary: Vec<Word>,
+    /// Position of each word in `dictionary`, by normalized spelling
+    index: HashMap<String, usize>,
+    /// Spellings of the words, by length
+    spellings: HashMap<usize, Arc<[Spelling]>>,
 }
 
 impl DictionaryService {
+    /// The built-in English dictionary
     pub async fn new() -> Result<Self> {
-        // Load and parse JSON file
-        let dictionary: WordData = serde_json::from_str(include_str!("../assets/dictionary.json"))?;
+        Self::load(
+            Language::english(),
+            include_str!("../assets/dictionary.json"),
+        )
+    }
+
+    /// Parses a JSON dictionary of `language`
+    pub fn load(language: Language, json: &str) -> Result<Self> {
+        let mut dictionary: WordData = serde_json::from_str(json)?;
+        for word in &mut dictionary.words {
+            word.word = language.uppercase(&word.word);
+        }
+        language.keep_spelled_with_alphabet(&mut dictionary.words);
+        count_frequencies(&mut dictionary.words);
+        normalize_definitions(&mut dictionary.words, &language);
+
+        // Keyed by normalized spelling, so words can be typed without their accents when the
+        // language ignores them. The first of the words spelled the same way wins.
+        let mut index = HashMap::new();
+        for (i, word) in dictionary.words.iter().enumerate() {
+            index.entry(language.normalize(&word.word)).or_insert(i);
+        }
+
+        let mut spellings: HashMap<usize, Vec<Spelling>> = HashMap::new();
+        for word in &dictionary.words {
+            spellings
+                .entry(word.word.chars().count())
+                .or_default()
+                .push(Spelling::new(&word.word, &language));
+        }
 
         Ok(Self {
+            language,
             dictionary: dictionary.words,
+            index,
+            spellings: spellings
+                .into_iter()
+                .map(|(length, list)| (length, list.into()))
+                .collect(),
         })
     }
+
+    /// Ranks the words by how common they are in `list`
+    pub fn with_frequency_list(mut self, list: &FrequencyList) -> Self {
+        list.apply(&mut self.dictionary);
+        self
+    }
 }
 
 #[async_trait]
 impl WordService for DictionaryService {
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
     async fn get_random_word(&self, length: usize) -> Result<Word> {
         let words = self.get_words_by_length(length).await?;
 
@@ -84,18 +323,42 @@ impl WordService for DictionaryService {
     }
 
     async fn validate_word(&self, word: &str) -> Result<bool> {
+        Ok(self.index.contains_key(&self.language.normalize(word)))
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
         Ok(self
-            .dictionary
-            .iter()
-            .any(|w| w.word == word.to_ascii_uppercase()))
+            .index
+            .get(&self.language.normalize(word))
+            .map(|&i| self.dictionary[i].clone()))
     }
 
     async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
         Ok(self
             .dictionary
             .iter()
-            .filter(|w| w.word.len() == length)
+            .filter(|w| w.word.chars().count() == length)
             .cloned()
             .collect())
     }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        Ok(self.spellings.get(&length).cloned().unwrap_or_default())
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        Ok(self.dictionary.clone())
+    }
+
+    async fn search(&self, query: &WordQuery) -> Result<WordPage> {
+        // Only clone the words that match
+        let matcher = query.matcher()?;
+        let words = self
+            .dictionary
+            .iter()
+            .filter(|w| matcher.matches(w))
+            .cloned()
+            .collect();
+        query.paginate(words)
+    }
 }
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::{Result, eyre::eyre};
+
+use crate::service::{
+    dictionary::{Spelling, Word, WordService},
+    language::Language,
+    search::{WordPage, WordQuery},
+};
+
+/// Asks each service in turn until one answers, e.g. a remote dictionary then the local one
+pub struct FallbackWordService {
+    services: Vec<Arc<dyn WordService>>,
+}
+
+impl FallbackWordService {
+    /// `services` must not be empty, the language of the first one is used
+    pub fn new(services: Vec<Arc<dyn WordService>>) -> Result<Self> {
+        if services.is_empty() {
+            return Err(eyre!("A fallback chain needs at least one word service"));
+        }
+        Ok(Self { services })
+    }
+}
+
+/// Runs `$call` on each service until one succeeds, returning the last error otherwise
+macro_rules! first_success {
+    ($self:ident, |$service:ident| $call:expr) => {{
+        let mut last_error = None;
+        for $service in &$self.services {
+            match $call.await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    eprintln!("Word service failed, trying the next one: {e:?}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.expect("Fallback chain is never empty"))
+    }};
+}
+
+#[async_trait]
+impl WordService for FallbackWordService {
+    fn language(&self) -> &Language {
+        self.services[0].language()
+    }
+
+    async fn get_random_word(&self, length: usize) -> Result<Word> {
+        first_success!(self, |service| service.get_random_word(length))
+    }
+
+    async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
+        first_success!(self, |service| service.get_words_by_length(length))
+    }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        first_success!(self, |service| service.get_spellings_by_length(length))
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        first_success!(self, |service| service.get_words())
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
+        first_success!(self, |service| service.get_word(word))
+    }
+
+    async fn validate_word(&self, word: &str) -> Result<bool> {
+        first_success!(self, |service| service.validate_word(word))
+    }
+
+    async fn search(&self, query: &WordQuery) -> Result<WordPage> {
+        first_success!(self, |service| service.search(query))
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use color_eyre::{Result, eyre::Context};
+use icu_normalizer::DecomposingNormalizerBorrowed;
+use serde::{Deserialize, Deserializer};
+
+use crate::service::{
+    commonness::FrequencyList,
+    dictionary::{DictionaryService, Word, WordService},
+};
+
+/// Name of the manifest file of a language pack
+const MANIFEST: &str = "language.json";
+
+/// Combining marks left after decomposing an accented letter, e.g. the grave accent of È
+const COMBINING_MARKS: [std::ops::RangeInclusive<char>; 4] = [
+    '\u{0300}'..='\u{036F}',
+    '\u{1AB0}'..='\u{1AFF}',
+    '\u{1DC0}'..='\u{1DFF}',
+    '\u{20D0}'..='\u{20FF}',
+];
+
+/// Uppercase form of a letter, keeping the letter when Unicode maps it to several (ß → SS)
+pub fn uppercase_letter(letter: char) -> char {
+    let mut upper = letter.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => letter,
+    }
+}
+
+/// Whether letters that only differ by their diacritics count as the same letter
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DiacriticPolicy {
+    /// È and E are different letters
+    #[default]
+    Distinct,
+    /// È can be typed as E, and scores as E
+    Ignored,
+}
+
+/// Letters and case rules of the words of a dictionary
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct Language {
+    /// Identifier used to select the language, e.g. "it"
+    pub id: String,
+    pub name: String,
+    /// Uppercase letters a word can be made of
+    #[serde(deserialize_with = "deserialize_alphabet")]
+    pub alphabet: Vec<char>,
+    /// Uppercase forms that differ from the Unicode ones, e.g. "ß": "ẞ" or "i": "İ"
+    #[serde(default)]
+    pub case_folding: HashMap<char, char>,
+    #[serde(default)]
+    pub diacritics: DiacriticPolicy,
+    /// Letters that stay apart from their base letter when diacritics are ignored, e.g. Ñ
+    #[serde(default, deserialize_with = "deserialize_alphabet")]
+    pub distinct_letters: Vec<char>,
+}
+
+fn deserialize_alphabet<'de, D>(deserializer: D) -> Result<Vec<char>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let alphabet: String = Deserialize::deserialize(deserializer)?;
+    Ok(alphabet
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(uppercase_letter)
+        .collect())
+}
+
+/// Id of the built-in language
+pub const ENGLISH: &str = "en";
+
+impl Language {
+    pub fn english() -> Self {
+        Self {
+            id: ENGLISH.to_string(),
+            name: "English".to_string(),
+            alphabet: ('A'..='Z').collect(),
+            case_folding: HashMap::new(),
+            diacritics: DiacriticPolicy::Distinct,
+            distinct_letters: Vec::new(),
+        }
+    }
+
+    pub fn uppercase_letter(&self, letter: char) -> char {
+        self.case_folding
+            .get(&letter)
+            .copied()
+            .unwrap_or_else(|| uppercase_letter(letter))
+    }
+
+    pub fn uppercase(&self, word: &str) -> String {
+        word.chars().map(|c| self.uppercase_letter(c)).collect()
+    }
+
+    /// Whether `word`, once uppercased, only uses letters of the alphabet
+    pub fn is_spelled_with_alphabet(&self, word: &str) -> bool {
+        word.chars()
+            .all(|c| self.alphabet.contains(&self.uppercase_letter(c)))
+    }
+
+    /// Drops the words using letters outside the alphabet, which players couldn't type, and
+    /// warns about them
+    pub fn keep_spelled_with_alphabet(&self, words: &mut Vec<Word>) {
+        let mut dropped = Vec::new();
+        words.retain(|word| {
+            let spelled = self.is_spelled_with_alphabet(&word.word);
+            if !spelled {
+                dropped.push(word.word.clone());
+            }
+            spelled
+        });
+        if !dropped.is_empty() {
+            eprintln!(
+                "Ignoring {} {} word(s) with letters outside the alphabet: {}",
+                dropped.len(),
+                self.name,
+                dropped.join(", ")
+            );
+        }
+    }
+
+    /// Uppercase letter that `letter` is compared as, following the diacritic policy
+    pub fn base_letter(&self, letter: char) -> char {
+        let letter = self.uppercase_letter(letter);
+        if self.diacritics == DiacriticPolicy::Distinct || self.distinct_letters.contains(&letter) {
+            return letter;
+        }
+
+        let mut buffer = [0; 4];
+        DecomposingNormalizerBorrowed::new_nfd()
+            .normalize(letter.encode_utf8(&mut buffer))
+            .chars()
+            .find(|c| !COMBINING_MARKS.iter().any(|marks| marks.contains(c)))
+            .unwrap_or(letter)
+    }
+
+    /// Form of `word` used to look it up and score it, e.g. CAFFE for caffè when diacritics are
+    /// ignored
+    pub fn normalize(&self, word: &str) -> String {
+        word.chars().map(|c| self.base_letter(c)).collect()
+    }
+}
+
+/// Manifest of a language pack directory, next to its dictionary
+#[derive(Deserialize)]
+struct LanguagePack {
+    #[serde(flatten)]
+    language: Language,
+    /// Dictionary in the same format as the built-in one, relative to the manifest
+    #[serde(default = "default_dictionary")]
+    dictionary: PathBuf,
+    /// Word frequency list used to rank the words by commonness
+    frequency_list: Option<PathBuf>,
+}
+
+fn default_dictionary() -> PathBuf {
+    PathBuf::from("dictionary.json")
+}
+
+impl LanguagePack {
+    /// Loads the dictionary of the pack in `directory`
+    fn load(directory: &Path) -> Result<DictionaryService> {
+        let manifest = directory.join(MANIFEST);
+        let text = fs::read_to_string(&manifest)
+            .wrap_err_with(|| format!("Can't read {}", manifest.display()))?;
+        let pack: LanguagePack = serde_json::from_str(&text)
+            .wrap_err_with(|| format!("Invalid language pack {}", manifest.display()))?;
+
+        let dictionary = directory.join(&pack.dictionary);
+        let words = fs::read_to_string(&dictionary)
+            .wrap_err_with(|| format!("Can't read {}", dictionary.display()))?;
+        let service = DictionaryService::load(pack.language, &words)
+            .wrap_err_with(|| format!("Invalid dictionary {}", dictionary.display()))?;
+
+        Ok(match pack.frequency_list {
+            Some(path) => service.with_frequency_list(&FrequencyList::load(&directory.join(path))?),
+            None => service,
+        })
+    }
+}
+
+/// Dictionaries of the available languages, looked up by language id
+#[derive(Clone)]
+pub struct DictionaryRegistry {
+    dictionaries: Vec<Arc<dyn WordService>>,
+}
+
+impl DictionaryRegistry {
+    pub fn empty() -> Self {
+        Self {
+            dictionaries: Vec::new(),
+        }
+    }
+
+    /// Registers a dictionary, replacing any dictionary of the same language
+    pub fn register(&mut self, dictionary: Arc<dyn WordService>) {
+        let id = dictionary.language().id.clone();
+        self.dictionaries.retain(|d| d.language().id != id);
+        self.dictionaries.push(dictionary);
+    }
+
+    /// Registers the language packs found in the subdirectories of `directory`
+    pub fn load_packs(&mut self, directory: &Path) -> Result<()> {
+        let entries = fs::read_dir(directory)
+            .wrap_err_with(|| format!("Can't read language directory {}", directory.display()))?;
+        let mut packs: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.join(MANIFEST).is_file())
+            .collect();
+        packs.sort();
+
+        for pack in packs {
+            self.register(Arc::new(LanguagePack::load(&pack)?));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn WordService>> {
+        self.dictionaries
+            .iter()
+            .find(|d| d.language().id == id)
+            .cloned()
+    }
+
+    pub fn services(&self) -> impl Iterator<Item = &Arc<dyn WordService>> {
+        self.dictionaries.iter()
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &Language> {
+        self.dictionaries.iter().map(|d| d.language())
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use color_eyre::{Result, eyre::Context};
+use serde::Deserialize;
+
+use crate::service::{
+    definition::Normalizer,
+    dictionary::{Spelling, SpellingCache, Word, WordData, WordService},
+    language::Language,
+};
+
+/// Layers file, listing the layers from the bottom (applied first) to the top
+///
+/// ```json
+/// {"layers": [{"add": "jargon.txt"}, {"remove": "blocklist.txt"}, {"definitions": "overlay.json"}]}
+/// ```
+#[derive(Deserialize)]
+struct LayersConfig {
+    layers: Vec<LayerConfig>,
+}
+
+/// One layer, with the path of its file relative to the layers file
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LayerConfig {
+    /// Words to add, one per line, or a dictionary in the built-in format to add them with
+    /// their definitions
+    Add(PathBuf),
+    /// Words to remove, one per line
+    Remove(PathBuf),
+    /// Dictionary in the built-in format whose definitions replace those of the lower layers
+    Definitions(PathBuf),
+}
+
+pub enum LayerKind {
+    Add(HashMap<String, Word>),
+    Remove(HashSet<String>),
+    Definitions(HashMap<String, Vec<String>>),
+}
+
+/// A change applied on top of the base dictionary and the layers below it
+pub struct Layer {
+    /// File the layer was loaded from
+    pub source: PathBuf,
+    pub kind: LayerKind,
+}
+
+impl Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (action, count) = match &self.kind {
+            LayerKind::Add(words) => ("adds", words.len()),
+            LayerKind::Remove(words) => ("removes", words.len()),
+            LayerKind::Definitions(words) => ("defines", words.len()),
+        };
+        write!(f, "{} {action} {count} word(s)", self.source.display())
+    }
+}
+
+/// Where the final version of a word comes from
+#[derive(PartialEq, Debug)]
+pub enum Origin {
+    Base,
+    /// Index of the layer that added the word
+    Added(usize),
+    /// Index of the layer that removed the word
+    Removed(usize),
+    Missing,
+}
+
+/// Stacks layers on a base dictionary, applied from the bottom up: a layer only adds words
+/// missing below it, so a word removed by a layer can be added back by a higher one, and
+/// a definitions layer replaces the definitions of the words present below it.
+pub struct LayeredWordService {
+    base: Arc<dyn WordService>,
+    layers: Vec<Layer>,
+    spellings: SpellingCache,
+}
+
+impl LayeredWordService {
+    pub fn new(base: Arc<dyn WordService>, layers: Vec<Layer>) -> Self {
+        Self {
+            base,
+            layers,
+            spellings: SpellingCache::default(),
+        }
+    }
+
+    /// Loads the layers listed in the JSON file at `path`
+    pub fn load(base: Arc<dyn WordService>, path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Can't read layers {}", path.display()))?;
+        let config: LayersConfig = serde_json::from_str(&text)
+            .wrap_err_with(|| format!("Invalid layers {}", path.display()))?;
+
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let language = base.language().clone();
+        let layers = config
+            .layers
+            .into_iter()
+            .map(|layer| Self::load_layer(&language, directory, layer))
+            .collect::<Result<Vec<Layer>>>()?;
+
+        Ok(Self::new(base, layers))
+    }
+
+    /// The layers file at `path` and the files of its layers, as far as it can be read
+    pub fn files(path: &Path) -> Vec<PathBuf> {
+        let mut files = vec![path.to_path_buf()];
+        let config = fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<LayersConfig>(&text).ok());
+        if let Some(config) = config {
+            let directory = path.parent().unwrap_or(Path::new("."));
+            files.extend(config.layers.into_iter().map(|layer| match layer {
+                LayerConfig::Add(source)
+                | LayerConfig::Remove(source)
+                | LayerConfig::Definitions(source) => directory.join(source),
+            }));
+        }
+        files
+    }
+
+    fn load_layer(language: &Language, directory: &Path, layer: LayerConfig) -> Result<Layer> {
+        let (source, kind) = match layer {
+            LayerConfig::Add(source) => {
+                let path = directory.join(&source);
+                let mut words: Vec<Word> = if path.extension().is_some_and(|ext| ext == "json") {
+                    read_dictionary(&path)?
+                        .into_iter()
+                        .map(|(word, meanings)| Self::word(language, &word, meanings))
+                        .collect()
+                } else {
+                    read_word_list(&path)?
+                        .into_iter()
+                        .map(|word| Self::word(language, &word, Vec::new()))
+                        .collect::<Vec<Word>>()
+                };
+                language.keep_spelled_with_alphabet(&mut words);
+                let words = words
+                    .into_iter()
+                    .map(|word| (language.normalize(&word.word), word))
+                    .collect();
+                (source, LayerKind::Add(words))
+            }
+            LayerConfig::Remove(source) => {
+                let words = read_word_list(&directory.join(&source))?
+                    .iter()
+                    .map(|word| language.normalize(word))
+                    .collect();
+                (source, LayerKind::Remove(words))
+            }
+            LayerConfig::Definitions(source) => {
+                let definitions = read_dictionary(&directory.join(&source))?
+                    .into_iter()
+                    .map(|(word, meanings)| (language.normalize(&word), meanings))
+                    .collect();
+                (source, LayerKind::Definitions(definitions))
+            }
+        };
+        Ok(Layer { source, kind })
+    }
+
+    fn word(language: &Language, word: &str, meanings: Vec<String>) -> Word {
+        let mut word = Word {
+            word: language.uppercase(word),
+            meanings: Vec::new(),
+            frequency: 0,
+            commonness: None,
+            definitions: Vec::new(),
+        };
+        Self::define(language, &mut word, meanings);
+        word
+    }
+
+    fn define(language: &Language, word: &mut Word, meanings: Vec<String>) {
+        word.definitions =
+            Normalizer::new(&HashSet::new(), language).normalize(&meanings.join("--"));
+        word.meanings = meanings;
+    }
+
+    pub fn base(&self) -> &Arc<dyn WordService> {
+        &self.base
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// The highest layer adding or removing `key`, if any
+    fn deciding_layer(&self, key: &str) -> Option<&Layer> {
+        self.layers.iter().rev().find(|layer| match &layer.kind {
+            LayerKind::Add(words) => words.contains_key(key),
+            LayerKind::Remove(words) => words.contains(key),
+            LayerKind::Definitions(_) => false,
+        })
+    }
+
+    /// Runs `word` through the layers, returning its final version and where it comes from
+    pub async fn resolve(&self, word: &str) -> Result<(Option<Word>, Origin)> {
+        let key = self.language().normalize(word);
+        let mut resolved = self.base.get_word(word).await?;
+        let mut origin = match resolved {
+            Some(_) => Origin::Base,
+            None => Origin::Missing,
+        };
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            match &layer.kind {
+                LayerKind::Add(words) => {
+                    if resolved.is_none()
+                        && let Some(word) = words.get(&key)
+                    {
+                        resolved = Some(word.clone());
+                        origin = Origin::Added(i);
+                    }
+                }
+                LayerKind::Remove(words) => {
+                    if words.contains(&key) {
+                        resolved = None;
+                        origin = Origin::Removed(i);
+                    }
+                }
+                LayerKind::Definitions(definitions) => {
+                    if let Some(word) = &mut resolved
+                        && let Some(meanings) = definitions.get(&key)
+                    {
+                        Self::define(self.language(), word, meanings.clone());
+                    }
+                }
+            }
+        }
+        Ok((resolved, origin))
+    }
+
+    /// Runs the words of the base through the layers, adding those `keep` accepts
+    fn apply(&self, mut words: Vec<Word>, keep: impl Fn(&Word) -> bool) -> Vec<Word> {
+        let language = self.language();
+
+        for layer in &self.layers {
+            match &layer.kind {
+                LayerKind::Add(added) => {
+                    let present: HashSet<String> = words
+                        .iter()
+                        .map(|word| language.normalize(&word.word))
+                        .collect();
+                    words.extend(
+                        added
+                            .iter()
+                            .filter(|(key, word)| !present.contains(*key) && keep(word))
+                            .map(|(_, word)| word.clone()),
+                    );
+                }
+                LayerKind::Remove(removed) => {
+                    words.retain(|word| !removed.contains(&language.normalize(&word.word)));
+                }
+                LayerKind::Definitions(definitions) => {
+                    for word in &mut words {
+                        if let Some(meanings) = definitions.get(&language.normalize(&word.word)) {
+                            Self::define(language, word, meanings.clone());
+                        }
+                    }
+                }
+            }
+        }
+        words
+    }
+}
+
+/// Words of a file with one word per line, skipping blank lines and `#` comments
+fn read_word_list(path: &Path) -> Result<Vec<String>> {
+    let text =
+        fs::read_to_string(path).wrap_err_with(|| format!("Can't read {}", path.display()))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+/// Words and meanings of a file in the format of the built-in dictionary
+fn read_dictionary(path: &Path) -> Result<Vec<(String, Vec<String>)>> {
+    let text =
+        fs::read_to_string(path).wrap_err_with(|| format!("Can't read {}", path.display()))?;
+    let data: WordData = serde_json::from_str(&text)
+        .wrap_err_with(|| format!("Invalid dictionary {}", path.display()))?;
+    Ok(data
+        .words
+        .into_iter()
+        .map(|word| (word.word, word.meanings))
+        .collect())
+}
+
+#[async_trait]
+impl WordService for LayeredWordService {
+    fn language(&self) -> &Language {
+        self.base.language()
+    }
+
+    async fn get_random_word(&self, length: usize) -> Result<Word> {
+        self.get_random_word_where(length, Default::default(), &|_| true)
+            .await
+    }
+
+    async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
+        let base = self.base.get_words_by_length(length).await?;
+        Ok(self.apply(base, |word| word.word.chars().count() == length))
+    }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        let source = self.base.get_spellings_by_length(length).await?;
+        let derive = async {
+            let language = self.language();
+            Ok(self
+                .get_words_by_length(length)
+                .await?
+                .iter()
+                .map(|word| Spelling::new(&word.word, language))
+                .collect())
+        };
+        self.spellings.get_or_derive(length, source, derive).await
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        let base = self.base.get_words().await?;
+        Ok(self.apply(base, |_| true))
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
+        Ok(self.resolve(word).await?.0)
+    }
+
+    async fn validate_word(&self, word: &str) -> Result<bool> {
+        let key = self.language().normalize(word);
+        match self.deciding_layer(&key) {
+            Some(layer) => Ok(matches!(layer.kind, LayerKind::Add(_))),
+            None => self.base.validate_word(word).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::service::dictionary::DictionaryService;
+
+    const BASE_WORDS: &str =
+        r#"{"words": {"crate": "A box", "cargo": "A load", "otter": "An animal"}}"#;
+
+    /// Base dictionary with, from the bottom up: TOKIO and SERDE added, CARGO and SERDE
+    /// removed, CARGO added back and CRATE added again, new definitions for OTTER and GHOST
+    fn layered() -> LayeredWordService {
+        let dir = std::env::temp_dir().join(format!("wordguessr-layers-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("jargon.txt"), "# Crates\ntokio\nserde\n").unwrap();
+        fs::write(dir.join("removed.txt"), "cargo\nserde\n").unwrap();
+        fs::write(
+            dir.join("readded.json"),
+            r#"{"words": {"cargo": "Freight", "crate": "Not the base one"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("overlay.json"),
+            r#"{"words": {"otter": "A swimming animal", "ghost": "Not in the dictionary"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("layers.json"),
+            r#"{"layers": [{"add": "jargon.txt"}, {"remove": "removed.txt"}, {"add": "readded.json"}, {"definitions": "overlay.json"}]}"#,
+        )
+        .unwrap();
+
+        let base = DictionaryService::load(Language::english(), BASE_WORDS).unwrap();
+        let layered = LayeredWordService::load(Arc::new(base), &dir.join("layers.json")).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+        layered
+    }
+
+    async fn meanings(layered: &LayeredWordService, word: &str) -> Option<Vec<String>> {
+        layered
+            .get_word(word)
+            .await
+            .unwrap()
+            .map(|word| word.meanings)
+    }
+
+    #[tokio::test]
+    async fn higher_layers_decide() {
+        let layered = layered();
+
+        assert_eq!(layered.resolve("CRATE").await.unwrap().1, Origin::Base);
+        assert_eq!(layered.resolve("TOKIO").await.unwrap().1, Origin::Added(0));
+        assert_eq!(
+            layered.resolve("SERDE").await.unwrap().1,
+            Origin::Removed(1)
+        );
+        // Removed by a layer, added back by a higher one
+        assert_eq!(layered.resolve("CARGO").await.unwrap().1, Origin::Added(2));
+        assert_eq!(layered.resolve("GHOST").await.unwrap().1, Origin::Missing);
+    }
+
+    #[tokio::test]
+    async fn added_words_dont_replace_present_ones() {
+        let layered = layered();
+
+        assert_eq!(meanings(&layered, "CRATE").await.unwrap(), ["A box"]);
+        assert_eq!(meanings(&layered, "CARGO").await.unwrap(), ["Freight"]);
+    }
+
+    #[tokio::test]
+    async fn definitions_only_replace_those_of_present_words() {
+        let layered = layered();
+
+        assert_eq!(
+            meanings(&layered, "OTTER").await.unwrap(),
+            ["A swimming animal"]
+        );
+        assert_eq!(meanings(&layered, "GHOST").await, None);
+        let words = layered.get_words().await.unwrap();
+        let otter = words.iter().find(|word| word.word == "OTTER").unwrap();
+        assert_eq!(otter.meanings, ["A swimming animal"]);
+    }
+
+    /// Word lists, lookups and validation resolve the layers the same way
+    #[tokio::test]
+    async fn lookups_agree_with_word_lists() {
+        let layered = layered();
+        let mut listed: Vec<String> = layered
+            .get_words_by_length(5)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|word| word.word)
+            .collect();
+        listed.sort();
+        assert_eq!(listed, ["CARGO", "CRATE", "OTTER", "TOKIO"]);
+
+        for word in ["CARGO", "CRATE", "GHOST", "OTTER", "SERDE", "TOKIO"] {
+            let is_listed = listed.iter().any(|listed| listed == word);
+            assert_eq!(
+                layered.validate_word(word).await.unwrap(),
+                is_listed,
+                "{word}"
+            );
+            assert_eq!(
+                layered.get_word(word).await.unwrap().is_some(),
+                is_listed,
+                "{word}"
+            );
+        }
+    }
+}
@@ -1 +1,12 @@
+pub mod blocklist;
+pub mod commonness;
+pub mod definition;
 pub mod dictionary;
+pub mod fallback;
+pub mod language;
+pub mod layered;
+pub mod pack;
+pub mod profile;
+pub mod reload;
+pub mod remote;
+pub mod search;
@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, OptionExt, bail},
+};
+use serde::Deserialize;
+
+use crate::{rules::RulesRegistry, service::language::DictionaryRegistry};
+
+/// Pack file, e.g. `animals.json`
+///
+/// ```json
+/// {"name": "Animals", "theme": "Creatures great and small", "tries": 8, "words": ["horse", "otter"]}
+/// ```
+#[derive(Deserialize)]
+struct PackFile {
+    name: String,
+    theme: Option<String>,
+    /// Language of the words, English by default
+    #[serde(default = "default_language")]
+    language: String,
+    /// Length all the words must have, games take the length of their word when missing
+    length: Option<usize>,
+    tries: Option<usize>,
+    mode: Option<String>,
+    words: Vec<String>,
+}
+
+fn default_language() -> String {
+    "en".into()
+}
+
+/// Named collection of secret words with its own game settings
+pub struct PuzzlePack {
+    /// Name of the pack file without its extension
+    pub id: String,
+    pub name: String,
+    pub theme: Option<String>,
+    pub language: String,
+    pub tries: Option<usize>,
+    pub mode: Option<String>,
+    /// Words of the pack as the dictionary spells them, looked up again for every game so
+    /// that reloads of the dictionaries and blocklist apply to them
+    pub words: Vec<String>,
+}
+
+impl PuzzlePack {
+    /// Loads the pack at `path`, looking its words up in the dictionary of its language
+    pub async fn load(
+        path: &Path,
+        dictionaries: &DictionaryRegistry,
+        rules: &RulesRegistry,
+    ) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Can't read pack {}", path.display()))?;
+        let file: PackFile = serde_json::from_str(&text)
+            .wrap_err_with(|| format!("Invalid pack {}", path.display()))?;
+        let id = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_eyre(format!("Invalid pack file name {}", path.display()))?
+            .to_string();
+
+        if file.tries == Some(0) {
+            bail!("Pack {id} must have at least one try");
+        }
+        let mode = match &file.mode {
+            Some(mode) => Some(
+                rules
+                    .get(mode)
+                    .ok_or_eyre(format!("Unknown game mode {mode} in pack {id}"))?,
+            ),
+            None => None,
+        };
+        if let Some(mode) = &mode
+            && !mode.keeps_secret()
+        {
+            bail!(
+                "Pack {id} can't be played in {} mode, which changes the secret word",
+                mode.name()
+            );
+        }
+
+        let dictionary = dictionaries
+            .get(&file.language)
+            .ok_or_eyre(format!("Unknown language {} in pack {id}", file.language))?;
+        let mut words = Vec::new();
+        let mut missing = Vec::new();
+        for word in &file.words {
+            match dictionary.get_word(word).await? {
+                Some(entry) => words.push(entry),
+                None => missing.push(word.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            bail!(
+                "Words of pack {id} missing from the dictionary or blocked: {}",
+                missing.join(", ")
+            );
+        }
+        if let Some(length) = file.length
+            && let Some(word) = words.iter().find(|w| w.word.chars().count() != length)
+        {
+            bail!("{} in pack {id} doesn't have {length} letters", word.word);
+        }
+        if let Some(mode) = &mode
+            && let Some(word) = words.iter().find(|word| !mode.accepts_secret(word))
+        {
+            bail!(
+                "{} in pack {id} can't be a secret word in {} mode",
+                word.word,
+                mode.name()
+            );
+        }
+        if words.is_empty() {
+            bail!("Pack {id} has no words");
+        }
+
+        Ok(Self {
+            id,
+            name: file.name,
+            theme: file.theme,
+            language: file.language,
+            tries: file.tries,
+            mode: file.mode,
+            words: words.into_iter().map(|word| word.word).collect(),
+        })
+    }
+}
+
+/// Puzzle packs players can pick from, in the order of their file names
+#[derive(Clone)]
+pub struct PackRegistry {
+    packs: Vec<Arc<PuzzlePack>>,
+}
+
+impl PackRegistry {
+    pub fn empty() -> Self {
+        Self { packs: Vec::new() }
+    }
+
+    /// Registers a pack, replacing any pack with the same id
+    pub fn register(&mut self, pack: Arc<PuzzlePack>) {
+        self.packs.retain(|p| p.id != pack.id);
+        self.packs.push(pack);
+    }
+
+    /// Registers the `.json` packs of `directory`
+    pub async fn load_dir(
+        &mut self,
+        directory: &Path,
+        dictionaries: &DictionaryRegistry,
+        rules: &RulesRegistry,
+    ) -> Result<()> {
+        let entries = fs::read_dir(directory)
+            .wrap_err_with(|| format!("Can't read pack directory {}", directory.display()))?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            self.register(Arc::new(
+                PuzzlePack::load(&file, dictionaries, rules).await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<PuzzlePack>> {
+        self.packs.iter().find(|p| p.id == id).cloned()
+    }
+
+    pub fn packs(&self) -> impl Iterator<Item = &Arc<PuzzlePack>> {
+        self.packs.iter()
+    }
+}
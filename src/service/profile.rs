@@ -0,0 +1,82 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use color_eyre::{Result, eyre::Context};
+use uuid::Uuid;
+
+use crate::model::profile::PlayerProfile;
+
+/// Player profiles, kept in memory and saved as one JSON file per player when given a directory
+pub struct ProfileStore {
+    directory: Option<PathBuf>,
+    profiles: Mutex<HashMap<Uuid, PlayerProfile>>,
+}
+
+impl ProfileStore {
+    /// Profiles that are lost when the server stops
+    pub fn in_memory() -> Self {
+        Self {
+            directory: None,
+            profiles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Profiles saved in `directory`, which is created if needed
+    pub fn open(directory: &Path) -> Result<Self> {
+        fs::create_dir_all(directory)
+            .wrap_err_with(|| format!("Can't create {}", directory.display()))?;
+        Ok(Self {
+            directory: Some(directory.to_path_buf()),
+            profiles: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn path(&self, player: Uuid) -> Option<PathBuf> {
+        self.directory
+            .as_ref()
+            .map(|directory| directory.join(format!("{player}.json")))
+    }
+
+    /// Profile from the file of the player, or a new one
+    fn read(&self, player: Uuid) -> PlayerProfile {
+        let Some(path) = self.path(player) else {
+            return PlayerProfile::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("Ignoring invalid profile {}: {e}", path.display());
+                PlayerProfile::default()
+            }),
+            Err(_) => PlayerProfile::default(),
+        }
+    }
+
+    pub fn get(&self, player: Uuid) -> PlayerProfile {
+        let mut profiles = self.profiles.lock().expect("Profiles lock poisoned");
+        profiles
+            .entry(player)
+            .or_insert_with(|| self.read(player))
+            .clone()
+    }
+
+    /// Changes the profile of `player` and saves it
+    pub fn update(&self, player: Uuid, update: impl FnOnce(&mut PlayerProfile)) -> Result<()> {
+        let mut profiles = self.profiles.lock().expect("Profiles lock poisoned");
+        let profile = profiles.entry(player).or_insert_with(|| self.read(player));
+        update(profile);
+
+        if let Some(path) = self.path(player) {
+            // Written next to the profile then renamed, so a crash never leaves half a file
+            let temporary = path.with_extension("json.tmp");
+            fs::write(&temporary, serde_json::to_string(profile)?)
+                .wrap_err_with(|| format!("Can't write {}", temporary.display()))?;
+            fs::rename(&temporary, &path)
+                .wrap_err_with(|| format!("Can't write {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail},
+};
+use tokio::runtime::Handle;
+
+use crate::service::{
+    commonness::Difficulty,
+    dictionary::{Spelling, Word, WordService},
+    language::{DictionaryRegistry, Language},
+    search::{WordPage, WordQuery},
+};
+
+/// Builds the dictionaries from their files, again on every reload
+#[async_trait]
+pub trait DictionaryLoader: Send + Sync {
+    async fn load(&self) -> Result<DictionaryRegistry>;
+    /// Files and directories whose changes trigger a reload
+    fn sources(&self) -> Vec<PathBuf>;
+}
+
+/// Word service that can be replaced while in use. Callers get the words of whichever
+/// service is current when they ask, and words they already hold (e.g. secrets) are unaffected.
+pub struct ReloadableWordService {
+    language: Language,
+    current: RwLock<Arc<dyn WordService>>,
+}
+
+impl ReloadableWordService {
+    pub fn new(service: Arc<dyn WordService>) -> Self {
+        Self {
+            language: service.language().clone(),
+            current: RwLock::new(service),
+        }
+    }
+
+    pub fn current(&self) -> Arc<dyn WordService> {
+        self.current
+            .read()
+            .expect("Word service lock poisoned")
+            .clone()
+    }
+
+    /// Swaps in `service`, which must be of the same language
+    fn replace(&self, service: Arc<dyn WordService>) {
+        *self.current.write().expect("Word service lock poisoned") = service;
+    }
+}
+
+#[async_trait]
+impl WordService for ReloadableWordService {
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    async fn get_random_word(&self, length: usize) -> Result<Word> {
+        self.current().get_random_word(length).await
+    }
+
+    async fn get_random_word_where(
+        &self,
+        length: usize,
+        difficulty: Difficulty,
+        filter: &(dyn for<'w> Fn(&'w Word) -> bool + Sync),
+    ) -> Result<Word> {
+        self.current()
+            .get_random_word_where(length, difficulty, filter)
+            .await
+    }
+
+    async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
+        self.current().get_words_by_length(length).await
+    }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        self.current().get_spellings_by_length(length).await
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        self.current().get_words().await
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
+        self.current().get_word(word).await
+    }
+
+    async fn validate_word(&self, word: &str) -> Result<bool> {
+        self.current().validate_word(word).await
+    }
+
+    async fn related_words(&self, word: &Word) -> Result<Vec<String>> {
+        self.current().related_words(word).await
+    }
+
+    async fn search(&self, query: &WordQuery) -> Result<WordPage> {
+        self.current().search(query).await
+    }
+}
+
+/// Size of a reloaded dictionary
+pub struct ReloadedDictionary {
+    pub language: String,
+    pub words: usize,
+}
+
+/// Last modification time of each watched file
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Keeps one `ReloadableWordService` per language and reloads them all together
+pub struct DictionaryReloader {
+    loader: Arc<dyn DictionaryLoader>,
+    dictionaries: Vec<Arc<ReloadableWordService>>,
+    /// Sources as of the last load, to tell when they change
+    fingerprint: Mutex<Fingerprint>,
+    /// Held during a reload so that two reloads don't interleave
+    reloading: tokio::sync::Mutex<()>,
+}
+
+impl DictionaryReloader {
+    pub async fn new(loader: Box<dyn DictionaryLoader>) -> Result<Self> {
+        let loader: Arc<dyn DictionaryLoader> = Arc::from(loader);
+        let fingerprint = fingerprint(&loader.sources());
+        let registry = loader.load().await?;
+        let dictionaries = registry
+            .services()
+            .map(|service| Arc::new(ReloadableWordService::new(service.clone())))
+            .collect();
+
+        Ok(Self {
+            loader,
+            dictionaries,
+            fingerprint: Mutex::new(fingerprint),
+            reloading: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Registry of the reloadable dictionaries, which always serve the latest words
+    pub fn registry(&self) -> DictionaryRegistry {
+        let mut registry = DictionaryRegistry::empty();
+        for dictionary in &self.dictionaries {
+            registry.register(dictionary.clone());
+        }
+        registry
+    }
+
+    /// Whether the sources changed since the last load
+    pub fn sources_changed(&self) -> bool {
+        let fingerprint = fingerprint(&self.loader.sources());
+        *self.fingerprint.lock().expect("Fingerprint lock poisoned") != fingerprint
+    }
+
+    /// Loads the dictionaries again and swaps them in, unless one of them fails to load,
+    /// changes language or has no words, in which case the current ones are kept
+    pub async fn reload(&self) -> Result<Vec<ReloadedDictionary>> {
+        let _reloading = self.reloading.lock().await;
+        let fingerprint = fingerprint(&self.loader.sources());
+        // Set first so that a broken file is only reported once, not on every poll
+        *self.fingerprint.lock().expect("Fingerprint lock poisoned") = fingerprint;
+
+        // Parsing the dictionaries is CPU heavy, keep it off the threads serving requests
+        let loader = self.loader.clone();
+        let registry =
+            tokio::task::spawn_blocking(move || Handle::current().block_on(loader.load()))
+                .await??;
+        let mut replacements = Vec::new();
+        let mut summary = Vec::new();
+        for dictionary in &self.dictionaries {
+            let id = &dictionary.language().id;
+            let replacement = registry.get(id).ok_or_eyre(format!(
+                "Language {id} is missing from the reloaded dictionaries"
+            ))?;
+            if replacement.language() != dictionary.language() {
+                bail!("The definition of language {id} changed, restart to apply it");
+            }
+            let words = replacement.get_words().await?.len();
+            if words == 0 {
+                bail!("The reloaded dictionary of language {id} has no words");
+            }
+            summary.push(ReloadedDictionary {
+                language: id.clone(),
+                words,
+            });
+            replacements.push(replacement);
+        }
+
+        for (dictionary, replacement) in self.dictionaries.iter().zip(replacements) {
+            dictionary.replace(replacement);
+        }
+        Ok(summary)
+    }
+}
+
+/// Modification times of the files at `paths`, including those under directories
+fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(path, &mut files);
+    }
+    files.sort();
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect()
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                collect_files(&entry.path(), files);
+            }
+        }
+        Err(_) => files.push(path.to_path_buf()),
+    }
+}
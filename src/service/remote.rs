@@ -0,0 +1,492 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_trait::async_trait;
+use color_eyre::{
+    Result,
+    eyre::{Context, bail, eyre},
+};
+use tokio::sync::OnceCell;
+
+use crate::service::{
+    dictionary::{DictionaryService, Spelling, Word, WordService},
+    language::Language,
+};
+
+/// Requests taking longer than this are abandoned
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Attempts after the first failed one
+const DEFAULT_RETRIES: usize = 2;
+/// Delay before the first retry, doubled for each following one
+const RETRY_DELAY: Duration = Duration::from_millis(200);
+/// After a failed fetch the remote is considered down for this long, so games don't wait for
+/// timeouts on every guess
+const COOLDOWN: Duration = Duration::from_secs(60);
+/// Cached lists older than this are fetched again, but still used if the remote is down
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Words fetched from an HTTP endpoint serving the dictionary format of the built-in one:
+/// `GET {endpoint}/words?length=5` for the words of a length and `GET {endpoint}/words` for
+/// all of them.
+///
+/// Responses are kept in memory and on disk, so a restart doesn't need the remote. Both copies
+/// are refreshed once they are older than the cache TTL, the old list being kept until the new
+/// one is loaded.
+pub struct RemoteWordService {
+    client: reqwest::Client,
+    endpoint: String,
+    language: Language,
+    retries: usize,
+    cache_dir: Option<PathBuf>,
+    cache_ttl: Duration,
+    /// Lists fetched so far, by word length (`None` for all the words)
+    lists: Mutex<HashMap<Option<usize>, Arc<CachedList>>>,
+    down_until: Mutex<Option<Instant>>,
+}
+
+/// A word list in memory, loaded once however many requests need it at the same time
+struct CachedList {
+    list: OnceCell<Arc<DictionaryService>>,
+    created: Instant,
+    /// The list this one refreshes, used until this one is loaded
+    previous: Option<Arc<DictionaryService>>,
+}
+
+impl CachedList {
+    fn new(previous: Option<Arc<DictionaryService>>) -> Self {
+        Self {
+            list: OnceCell::new(),
+            created: Instant::now(),
+            previous,
+        }
+    }
+}
+
+impl RemoteWordService {
+    pub fn new(endpoint: &str, language: Language) -> Result<Self> {
+        Ok(Self {
+            client: Self::client(DEFAULT_TIMEOUT)?,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            language,
+            retries: DEFAULT_RETRIES,
+            cache_dir: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            lists: Mutex::new(HashMap::new()),
+            down_until: Mutex::new(None),
+        })
+    }
+
+    fn client(timeout: Duration) -> Result<reqwest::Client> {
+        Ok(reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout)
+            .build()?)
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.client = Self::client(timeout)?;
+        Ok(self)
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Keeps the fetched lists in `cache_dir`
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Fetches the lists again once they are older than `cache_ttl`
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    async fn list(&self, length: Option<usize>) -> Result<Arc<DictionaryService>> {
+        let cached = {
+            let mut lists = self.lists.lock().expect("Lists lock poisoned");
+            let cached = lists
+                .entry(length)
+                .or_insert_with(|| Arc::new(CachedList::new(None)));
+            if let Some(list) = cached.list.get()
+                && cached.created.elapsed() >= self.cache_ttl
+            {
+                *cached = Arc::new(CachedList::new(Some(list.clone())));
+            }
+            cached.clone()
+        };
+
+        match cached.list.get_or_try_init(|| self.load(length)).await {
+            Ok(list) => Ok(list.clone()),
+            Err(e) => match &cached.previous {
+                Some(previous) => {
+                    eprintln!("Error refreshing word list, keeping the previous one: {e:?}");
+                    Ok(previous.clone())
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn load(&self, length: Option<usize>) -> Result<Arc<DictionaryService>> {
+        let json = self.fetch(length).await?;
+        let language = self.language.clone();
+        // Parsing a list is CPU heavy, keep it off the threads serving requests
+        let list =
+            tokio::task::spawn_blocking(move || DictionaryService::load(language, &json)).await??;
+        Ok(Arc::new(list))
+    }
+
+    /// Body of the list, from the disk cache while it's fresh, from the remote otherwise
+    async fn fetch(&self, length: Option<usize>) -> Result<String> {
+        let cache_file = self.cache_dir.as_ref().map(|dir| {
+            let name = match length {
+                Some(length) => format!("{}-{length}.json", self.language.id),
+                None => format!("{}-all.json", self.language.id),
+            };
+            dir.join(name)
+        });
+
+        if let Some(file) = &cache_file
+            && let Ok(modified) = fs::metadata(file).and_then(|m| m.modified())
+            && SystemTime::now()
+                .duration_since(modified)
+                .is_ok_and(|age| age < self.cache_ttl)
+        {
+            return Ok(tokio::fs::read_to_string(file).await?);
+        }
+
+        match self.fetch_remote(length).await {
+            Ok(json) => {
+                if let Some(file) = &cache_file
+                    && let Err(e) = Self::write_cache(file, &json).await
+                {
+                    eprintln!("Error caching word list: {e:?}");
+                }
+                Ok(json)
+            }
+            // A stale copy is better than nothing
+            Err(e) => match cache_file {
+                Some(file) => tokio::fs::read_to_string(file).await.or(Err(e)),
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn write_cache(file: &Path, json: &str) -> Result<()> {
+        if let Some(dir) = file.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(file, json)
+            .await
+            .wrap_err_with(|| format!("Can't write cache {}", file.display()))
+    }
+
+    async fn fetch_remote(&self, length: Option<usize>) -> Result<String> {
+        let url = format!("{}/words", self.endpoint);
+        let down_until = *self.down_until.lock().expect("Down lock poisoned");
+        if down_until.is_some_and(|until| Instant::now() < until) {
+            bail!("{url} is down");
+        }
+
+        let mut delay = RETRY_DELAY;
+        let mut attempt = 0;
+
+        loop {
+            let mut request = self.client.get(&url);
+            if let Some(length) = length {
+                request = request.query(&[("length", length)]);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    return Ok(response.text().await?);
+                }
+                // Client errors won't go away by asking again
+                Ok(response) if response.status().is_client_error() => {
+                    bail!("{url} answered {}", response.status())
+                }
+                Ok(response) => eyre!("{url} answered {}", response.status()),
+                Err(e) => eyre!(e).wrap_err(format!("Can't reach {url}")),
+            };
+
+            if attempt >= self.retries {
+                *self.down_until.lock().expect("Down lock poisoned") =
+                    Some(Instant::now() + COOLDOWN);
+                return Err(error);
+            }
+            attempt += 1;
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+}
+
+#[async_trait]
+impl WordService for RemoteWordService {
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    async fn get_random_word(&self, length: usize) -> Result<Word> {
+        self.list(Some(length)).await?.get_random_word(length).await
+    }
+
+    async fn get_words_by_length(&self, length: usize) -> Result<Vec<Word>> {
+        self.list(Some(length))
+            .await?
+            .get_words_by_length(length)
+            .await
+    }
+
+    async fn get_spellings_by_length(&self, length: usize) -> Result<Arc<[Spelling]>> {
+        self.list(Some(length))
+            .await?
+            .get_spellings_by_length(length)
+            .await
+    }
+
+    async fn get_words(&self) -> Result<Vec<Word>> {
+        self.list(None).await?.get_words().await
+    }
+
+    async fn get_word(&self, word: &str) -> Result<Option<Word>> {
+        self.list(Some(word.chars().count()))
+            .await?
+            .get_word(word)
+            .await
+    }
+
+    async fn validate_word(&self, word: &str) -> Result<bool> {
+        self.list(Some(word.chars().count()))
+            .await?
+            .validate_word(word)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Router, extract::State, http::StatusCode, routing::get};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::service::fallback::FallbackWordService;
+
+    const REMOTE_WORDS: &str = r#"{"words": {"crate": "A box", "cargo": "A load"}}"#;
+    const CACHED_WORDS: &str = r#"{"words": {"cache": "A hiding place"}}"#;
+
+    /// Answers with `statuses` in turn, repeating the last one, and counts the requests
+    #[derive(Clone)]
+    struct MockRemote {
+        statuses: Arc<Vec<StatusCode>>,
+        delay: Duration,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockRemote {
+        fn new(statuses: &[StatusCode]) -> Self {
+            Self {
+                statuses: Arc::new(statuses.to_vec()),
+                delay: Duration::ZERO,
+                requests: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = delay;
+            self
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+
+        /// Serves the mock on a local port, returning its endpoint
+        async fn serve(&self) -> String {
+            async fn words(State(mock): State<MockRemote>) -> (StatusCode, &'static str) {
+                let i = mock.requests.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(mock.delay).await;
+                let status = mock.statuses[i.min(mock.statuses.len() - 1)];
+                (status, REMOTE_WORDS)
+            }
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let app = Router::new()
+                .route("/words", get(words))
+                .with_state(self.clone());
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            format!("http://{address}")
+        }
+    }
+
+    fn cache_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("wordguessr-remote-{}", Uuid::new_v4()))
+    }
+
+    fn write_cached_list(dir: &Path, age: Duration) {
+        fs::create_dir_all(dir).unwrap();
+        let file = dir.join("en-5.json");
+        fs::write(&file, CACHED_WORDS).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+    }
+
+    fn words(list: &[Word]) -> Vec<&str> {
+        let mut words: Vec<&str> = list.iter().map(|w| w.word.as_str()).collect();
+        words.sort();
+        words
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let mock = MockRemote::new(&[StatusCode::INTERNAL_SERVER_ERROR, StatusCode::OK]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english()).unwrap();
+
+        let list = remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(words(&list), ["CARGO", "CRATE"]);
+        assert_eq!(mock.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn server_errors_start_a_cooldown() {
+        let mock = MockRemote::new(&[StatusCode::SERVICE_UNAVAILABLE]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english()).unwrap();
+
+        assert!(remote.get_words_by_length(5).await.is_err());
+        assert_eq!(mock.requests(), DEFAULT_RETRIES + 1);
+
+        // The remote isn't asked again while it is considered down
+        assert!(remote.get_words_by_length(5).await.is_err());
+        assert_eq!(mock.requests(), DEFAULT_RETRIES + 1);
+        assert!(remote.down_until.lock().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let mock = MockRemote::new(&[StatusCode::NOT_FOUND, StatusCode::OK]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english()).unwrap();
+
+        assert!(remote.get_words_by_length(5).await.is_err());
+        assert_eq!(mock.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn slow_answers_time_out() {
+        let mock = MockRemote::new(&[StatusCode::OK]).with_delay(Duration::from_secs(5));
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_timeout(Duration::from_millis(100))
+            .unwrap()
+            .with_retries(0);
+
+        let start = Instant::now();
+        assert!(remote.get_words_by_length(5).await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn fresh_cache_is_used_without_a_request() {
+        let mock = MockRemote::new(&[StatusCode::OK]);
+        let dir = cache_dir();
+        write_cached_list(&dir, Duration::ZERO);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_cache_dir(dir.clone());
+
+        let list = remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(words(&list), ["CACHE"]);
+        assert_eq!(mock.requests(), 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stale_cache_is_used_when_the_remote_is_down() {
+        let mock = MockRemote::new(&[StatusCode::INTERNAL_SERVER_ERROR]);
+        let dir = cache_dir();
+        write_cached_list(&dir, DEFAULT_CACHE_TTL * 2);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_retries(0)
+            .with_cache_dir(dir.clone());
+
+        let list = remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(words(&list), ["CACHE"]);
+        assert_eq!(mock.requests(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_fetch_once() {
+        let mock = MockRemote::new(&[StatusCode::OK]).with_delay(Duration::from_millis(200));
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english()).unwrap();
+
+        let (first, second) =
+            tokio::join!(remote.get_words_by_length(5), remote.get_words_by_length(5));
+        assert_eq!(words(&first.unwrap()), ["CARGO", "CRATE"]);
+        assert_eq!(words(&second.unwrap()), ["CARGO", "CRATE"]);
+        assert_eq!(mock.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn old_lists_are_refreshed() {
+        let mock = MockRemote::new(&[StatusCode::OK]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_cache_ttl(Duration::from_millis(100));
+
+        remote.get_words_by_length(5).await.unwrap();
+        remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(mock.requests(), 1);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(mock.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_refreshes_keep_the_previous_list() {
+        let mock = MockRemote::new(&[StatusCode::OK, StatusCode::INTERNAL_SERVER_ERROR]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_retries(0)
+            .with_cache_ttl(Duration::from_millis(100));
+
+        remote.get_words_by_length(5).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let list = remote.get_words_by_length(5).await.unwrap();
+        assert_eq!(words(&list), ["CARGO", "CRATE"]);
+        assert_eq!(mock.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn fallback_answers_from_the_local_dictionary() {
+        let mock = MockRemote::new(&[StatusCode::INTERNAL_SERVER_ERROR]);
+        let remote = RemoteWordService::new(&mock.serve().await, Language::english())
+            .unwrap()
+            .with_retries(0);
+        let local =
+            DictionaryService::load(Language::english(), r#"{"words": {"local": "Nearby"}}"#)
+                .unwrap();
+        let fallback = FallbackWordService::new(vec![Arc::new(remote), Arc::new(local)]).unwrap();
+
+        let list = fallback.get_words_by_length(5).await.unwrap();
+        assert_eq!(words(&list), ["LOCAL"]);
+        assert_eq!(mock.requests(), 1);
+    }
+}
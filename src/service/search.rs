@@ -0,0 +1,202 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use color_eyre::{Result, eyre::bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    service::{dictionary::Word, language::uppercase_letter},
+    solver::constraints::Constraints,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+/// Characters that stand for an unknown letter in a pattern
+const WILDCARDS: [char; 3] = ['?', '_', '.'];
+
+/// Reads empty form fields as missing values
+fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value: Option<String> = Deserialize::deserialize(deserializer)?;
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_per_page() -> usize {
+    DEFAULT_PAGE_SIZE
+}
+
+/// Search over the dictionary, used by the `/api/words` endpoint and the `search` command
+#[derive(Deserialize, Serialize, clap::Args, Clone, Default, Debug)]
+pub struct WordQuery {
+    /// Known letters by position, with `?` for unknown ones (e.g. `?A??E`)
+    #[arg(short, long)]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub pattern: Option<String>,
+    /// Beginning of the word
+    #[arg(long)]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub prefix: Option<String>,
+    /// Word length, implied by the pattern when there is one
+    #[arg(short, long)]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub length: Option<usize>,
+    /// Letters the word must contain
+    #[arg(short, long, default_value = "")]
+    #[serde(default)]
+    pub include: String,
+    /// Letters the word must not contain
+    #[arg(short, long, default_value = "")]
+    #[serde(default)]
+    pub exclude: String,
+    /// Letters present but not at a 1-based position, e.g. `R2,E5`
+    #[arg(short, long, default_value = "")]
+    #[serde(default)]
+    pub misplaced: String,
+    /// Page number, starting from 1
+    #[arg(long, default_value_t = default_page())]
+    #[serde(default = "default_page")]
+    pub page: usize,
+    /// Words per page
+    #[arg(long, default_value_t = default_per_page())]
+    #[serde(default = "default_per_page")]
+    pub per_page: usize,
+}
+
+/// Checks words against a `WordQuery`
+pub struct WordMatcher {
+    prefix: String,
+    length: Option<usize>,
+    constraints: Constraints,
+}
+
+impl WordMatcher {
+    pub fn matches(&self, word: &Word) -> bool {
+        let letters: Vec<char> = word.word.chars().collect();
+        word.word.starts_with(&self.prefix)
+            && self.length.is_none_or(|length| letters.len() == length)
+            && self.constraints.matches(&letters)
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WordMatch {
+    pub word: String,
+    pub frequency: usize,
+}
+
+/// One page of search results
+#[derive(Serialize, Clone, Debug)]
+pub struct WordPage {
+    /// Number of matching words over all pages
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+    pub words: Vec<WordMatch>,
+}
+
+impl WordQuery {
+    pub fn length(&self) -> Option<usize> {
+        self.pattern
+            .as_ref()
+            .map(|pattern| pattern.chars().count())
+            .or(self.length)
+    }
+
+    pub fn matcher(&self) -> Result<WordMatcher> {
+        let pattern = self.pattern.as_deref().unwrap_or("");
+        if let (Some(length), false) = (self.length, pattern.is_empty())
+            && pattern.chars().count() != length
+        {
+            bail!("The pattern doesn't have {length} letters");
+        }
+
+        let mut constraints = Constraints::default();
+        // How many times each letter is known to occur, so excluding it doesn't clash
+        let mut required: HashMap<char, usize> = HashMap::new();
+
+        for (i, letter) in pattern.chars().enumerate() {
+            if !WILDCARDS.contains(&letter) {
+                let letter = uppercase_letter(letter);
+                constraints.fix(i, letter);
+                *required.entry(letter).or_default() += 1;
+            }
+        }
+
+        for letter in self.include.chars().filter(|c| c.is_alphabetic()) {
+            *required.entry(uppercase_letter(letter)).or_default() += 1;
+        }
+
+        for entry in self
+            .misplaced
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let mut chars = entry.chars();
+            let (Some(letter), Ok(position)) = (chars.next(), chars.as_str().parse::<usize>())
+            else {
+                bail!("Invalid misplaced letter {entry}, expected a letter and a position like R2");
+            };
+            if position == 0 {
+                bail!("Positions start from 1 in {entry}");
+            }
+            let letter = uppercase_letter(letter);
+            constraints.exclude_at(position - 1, letter);
+            let count = required.entry(letter).or_default();
+            *count = (*count).max(1);
+        }
+
+        for (letter, count) in &required {
+            constraints.require(*letter, *count);
+        }
+        for letter in self.exclude.chars().filter(|c| c.is_alphabetic()) {
+            let letter = uppercase_letter(letter);
+            constraints.limit(letter, required.get(&letter).copied().unwrap_or(0));
+        }
+
+        Ok(WordMatcher {
+            prefix: self.prefix.as_deref().unwrap_or("").to_uppercase(),
+            length: self.length(),
+            constraints,
+        })
+    }
+
+    /// Keeps the matching words and returns the requested page, most frequent words first
+    pub fn paginate(&self, words: Vec<Word>) -> Result<WordPage> {
+        if self.page == 0 {
+            bail!("Pages start from 1");
+        }
+        let per_page = self.per_page.clamp(1, MAX_PAGE_SIZE);
+
+        let matcher = self.matcher()?;
+        let mut words: Vec<Word> = words.into_iter().filter(|w| matcher.matches(w)).collect();
+        words.sort_by(|a, b| b.frequency.cmp(&a.frequency).then(a.word.cmp(&b.word)));
+
+        Ok(WordPage {
+            total: words.len(),
+            page: self.page,
+            per_page,
+            words: words
+                .into_iter()
+                // Pages past the end are empty, however large
+                .skip((self.page - 1).saturating_mul(per_page))
+                .take(per_page)
+                .map(|w| WordMatch {
+                    word: w.word,
+                    frequency: w.frequency,
+                })
+                .collect(),
+        })
+    }
+}
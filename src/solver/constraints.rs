@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::{
+    model::cell::CellState,
+    solver::{Feedback, Observation},
+};
+
+/// Feedback of several guesses compiled into per-position and per-letter rules, so that
+/// checking a word doesn't need to score it against every guess again
+#[derive(Clone, Default, Debug)]
+pub struct Constraints {
+    /// Letter known to be at each position
+    fixed: Vec<Option<char>>,
+    /// Letters known not to be at each position
+    excluded: Vec<Vec<char>>,
+    /// Minimum number of times each letter occurs
+    min_counts: HashMap<char, usize>,
+    /// Maximum number of times each letter occurs
+    max_counts: HashMap<char, usize>,
+    /// Feedback that can't be compiled, checked the slow way
+    opaque: Vec<Observation>,
+    /// Some feedback can't be produced by any word
+    impossible: bool,
+}
+
+impl Constraints {
+    pub fn new(observations: &[Observation]) -> Self {
+        let mut constraints = Self::default();
+        for observation in observations {
+            constraints.add(observation);
+        }
+        constraints
+    }
+
+    fn position(&mut self, i: usize) {
+        if self.fixed.len() <= i {
+            self.fixed.resize(i + 1, None);
+            self.excluded.resize(i + 1, Vec::new());
+        }
+    }
+
+    /// The letter is at position `i`
+    pub fn fix(&mut self, i: usize, letter: char) {
+        self.position(i);
+        if self.fixed[i].is_some_and(|fixed| fixed != letter) {
+            self.impossible = true;
+        }
+        self.fixed[i] = Some(letter);
+    }
+
+    /// The letter isn't at position `i`
+    pub fn exclude_at(&mut self, i: usize, letter: char) {
+        self.position(i);
+        self.excluded[i].push(letter);
+    }
+
+    /// The letter occurs at least `count` times
+    pub fn require(&mut self, letter: char, count: usize) {
+        let min = self.min_counts.entry(letter).or_default();
+        *min = (*min).max(count);
+    }
+
+    /// The letter occurs at most `count` times
+    pub fn limit(&mut self, letter: char, count: usize) {
+        let max = self.max_counts.entry(letter).or_insert(usize::MAX);
+        *max = (*max).min(count);
+    }
+
+    pub fn add(&mut self, observation: &Observation) {
+        let Feedback::Tiles(tiles) = &observation.feedback else {
+            self.opaque.push(observation.clone());
+            return;
+        };
+
+        // Letters are marked present from left to right, so a present tile can't follow an
+        // absent tile of the same letter
+        let mut absent_seen: Vec<char> = Vec::new();
+        for (letter, state) in observation.guess.iter().zip(tiles) {
+            match state {
+                CellState::Present if absent_seen.contains(letter) => self.impossible = true,
+                CellState::Absent => absent_seen.push(*letter),
+                _ => {}
+            }
+        }
+
+        let mut confirmed: HashMap<char, usize> = HashMap::new();
+        for (i, (letter, state)) in observation.guess.iter().zip(tiles).enumerate() {
+            match state {
+                CellState::Correct => {
+                    self.fix(i, *letter);
+                    *confirmed.entry(*letter).or_default() += 1;
+                }
+                CellState::Present => {
+                    self.exclude_at(i, *letter);
+                    *confirmed.entry(*letter).or_default() += 1;
+                }
+                _ => self.exclude_at(i, *letter),
+            }
+        }
+
+        for (letter, count) in &confirmed {
+            self.require(*letter, *count);
+        }
+
+        // An absent tile means the secret has no more of that letter than the confirmed ones
+        for (letter, state) in observation.guess.iter().zip(tiles) {
+            if *state == CellState::Absent {
+                self.limit(*letter, confirmed.get(letter).copied().unwrap_or(0));
+            }
+        }
+    }
+
+    pub fn matches(&self, word: &[char]) -> bool {
+        if self.impossible {
+            return false;
+        }
+
+        let positional = word.iter().enumerate().all(|(i, letter)| {
+            self.fixed
+                .get(i)
+                .is_none_or(|fixed| fixed.is_none_or(|f| f == *letter))
+                && self
+                    .excluded
+                    .get(i)
+                    .is_none_or(|excluded| !excluded.contains(letter))
+        });
+        if !positional || self.fixed.len() > word.len() {
+            return false;
+        }
+
+        let count = |letter: &char| word.iter().filter(|c| *c == letter).count();
+        self.min_counts
+            .iter()
+            .all(|(letter, min)| count(letter) >= *min)
+            && self
+                .max_counts
+                .iter()
+                .all(|(letter, max)| count(letter) <= *max)
+            && self
+                .opaque
+                .iter()
+                .all(|observation| observation.is_consistent_with(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every word of `length` letters over `alphabet`
+    fn words(alphabet: &[char], length: usize) -> Vec<Vec<char>> {
+        (0..length).fold(vec![Vec::new()], |words, _| {
+            words
+                .iter()
+                .flat_map(|word| {
+                    alphabet.iter().map(move |letter| {
+                        let mut word = word.clone();
+                        word.push(*letter);
+                        word
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Every tile pattern of `length` tiles
+    fn patterns(length: usize) -> Vec<Vec<CellState>> {
+        let states = [CellState::Correct, CellState::Present, CellState::Absent];
+        (0..length).fold(vec![Vec::new()], |patterns, _| {
+            patterns
+                .iter()
+                .flat_map(|pattern| {
+                    states.iter().map(move |state| {
+                        let mut pattern = pattern.clone();
+                        pattern.push(state.clone());
+                        pattern
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn observations(length: usize) -> Vec<Observation> {
+        let guesses = words(&['A', 'B', 'C'], length);
+        guesses
+            .iter()
+            .flat_map(|guess| {
+                patterns(length).into_iter().map(|tiles| Observation {
+                    guess: guess.clone(),
+                    feedback: Feedback::Tiles(tiles),
+                })
+            })
+            .collect()
+    }
+
+    /// The compiled rules must agree with scoring the word, including repeated letters
+    #[test]
+    fn matches_agrees_with_scoring() {
+        let secrets = words(&['A', 'B', 'C'], 3);
+        for observation in observations(3) {
+            let constraints = Constraints::new(std::slice::from_ref(&observation));
+            for secret in &secrets {
+                assert_eq!(
+                    constraints.matches(secret),
+                    observation.is_consistent_with(secret),
+                    "{:?} {:?} {secret:?}",
+                    observation.guess,
+                    observation.feedback,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn combined_feedback_agrees_with_scoring() {
+        let secrets = words(&['A', 'B', 'C'], 2);
+        let observations = observations(2);
+        for first in &observations {
+            for second in &observations {
+                let both = [first.clone(), second.clone()];
+                let constraints = Constraints::new(&both);
+                for secret in &secrets {
+                    let consistent = both.iter().all(|o| o.is_consistent_with(secret));
+                    assert_eq!(
+                        constraints.matches(secret),
+                        consistent,
+                        "{both:?} {secret:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+    thread,
+};
+
+pub mod constraints;
+
+use crate::{
+    model::{
+        analysis::{Analysis, GuessAnalysis},
+        cell::CellState,
+        grid::Grid,
+        row::{Row, RowSummary},
+    },
+    rules::determine_cell_states,
+    service::{dictionary::Spelling, language::uppercase_letter},
+    solver::constraints::Constraints,
+};
+
+/// Encodes the feedback of a guess as a base-3 number, one digit per letter.
+///
+/// Built on `determine_cell_states` so the solver scores guesses exactly like the game does.
+pub fn pattern(guess: &[char], secret: &[char]) -> u32 {
+    determine_cell_states(guess, secret)
+        .iter()
+        .fold(0, |code, state| {
+            let digit = match state {
+                CellState::Correct => 2,
+                CellState::Present => 1,
+                _ => 0,
+            };
+            code * 3 + digit
+        })
+}
+
+/// Feedback received for a guess
+#[derive(Clone, PartialEq, Debug)]
+pub enum Feedback {
+    /// The state of every letter
+    Tiles(Vec<CellState>),
+    /// Only the number of correct and present letters
+    Counts(RowSummary),
+}
+
+impl Feedback {
+    pub fn kind(&self) -> FeedbackKind {
+        match self {
+            Feedback::Tiles(_) => FeedbackKind::Tiles,
+            Feedback::Counts(_) => FeedbackKind::Counts,
+        }
+    }
+}
+
+/// What players are told about their guesses, which decides how informative a guess is
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FeedbackKind {
+    Tiles,
+    Counts,
+}
+
+impl FeedbackKind {
+    /// Kind of the feedback received so far, tiles when there is none
+    pub fn of(observations: &[Observation]) -> Self {
+        observations
+            .first()
+            .map_or(FeedbackKind::Tiles, |observation| {
+                observation.feedback.kind()
+            })
+    }
+
+    /// Encodes the feedback `guess` would get if `secret` were the secret word
+    pub fn pattern(self, guess: &[char], secret: &[char]) -> u32 {
+        match self {
+            FeedbackKind::Tiles => pattern(guess, secret),
+            FeedbackKind::Counts => {
+                let summary = RowSummary::from(determine_cell_states(guess, secret).as_slice());
+                (summary.correct * (guess.len() + 1) + summary.present) as u32
+            }
+        }
+    }
+}
+
+/// A scored guess, the input of the solver
+#[derive(Clone, PartialEq, Debug)]
+pub struct Observation {
+    pub guess: Vec<char>,
+    pub feedback: Feedback,
+}
+
+impl Observation {
+    /// Reads the feedback of a scored row, `None` if the row wasn't played yet
+    pub fn from_row(row: &Row) -> Option<Self> {
+        if !row.is_scored() {
+            return None;
+        }
+
+        let feedback = match row.summary {
+            Some(summary) => Feedback::Counts(summary),
+            None => Feedback::Tiles(row.states()),
+        };
+        Some(Self {
+            guess: row.letters(),
+            feedback,
+        })
+    }
+
+    pub fn from_grid(grid: &Grid) -> Vec<Self> {
+        grid.rows.iter().filter_map(Self::from_row).collect()
+    }
+
+    /// Whether `candidate` would have produced this feedback if it were the secret
+    pub fn is_consistent_with(&self, candidate: &[char]) -> bool {
+        if candidate.len() != self.guess.len() {
+            return false;
+        }
+
+        let states = determine_cell_states(&self.guess, candidate);
+        match &self.feedback {
+            Feedback::Tiles(tiles) => states == *tiles,
+            Feedback::Counts(summary) => RowSummary::from(states.as_slice()) == *summary,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseObservationError(String);
+
+impl Display for ParseObservationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseObservationError {}
+
+/// Parses `GUESS=FEEDBACK`, with `g` for correct, `y` for present and `.` for absent letters
+/// (e.g. `crane=..y.g`), or `GUESS=C/P` with the number of correct and present letters
+impl FromStr for Observation {
+    type Err = ParseObservationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (guess, feedback) = s
+            .split_once('=')
+            .ok_or_else(|| ParseObservationError(format!("Missing '=' in {s}")))?;
+        let guess: Vec<char> = guess.chars().map(uppercase_letter).collect();
+
+        let feedback = if let Some((correct, present)) = feedback.split_once('/') {
+            let parse = |count: &str| {
+                count
+                    .parse::<usize>()
+                    .map_err(|_| ParseObservationError(format!("Invalid count in {s}")))
+            };
+            Feedback::Counts(RowSummary {
+                correct: parse(correct)?,
+                present: parse(present)?,
+            })
+        } else {
+            let tiles = feedback
+                .chars()
+                .map(|c| match c.to_ascii_lowercase() {
+                    'g' => Ok(CellState::Correct),
+                    'y' => Ok(CellState::Present),
+                    '.' | 'x' | '-' => Ok(CellState::Absent),
+                    _ => Err(ParseObservationError(format!(
+                        "Invalid feedback letter '{c}' in {s}"
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if tiles.len() != guess.len() {
+                return Err(ParseObservationError(format!(
+                    "Feedback and guess have different lengths in {s}"
+                )));
+            }
+            Feedback::Tiles(tiles)
+        };
+
+        Ok(Self { guess, feedback })
+    }
+}
+
+/// A possible next guess and the information it is expected to give
+#[derive(Clone, Debug)]
+pub struct RankedGuess {
+    pub word: String,
+    /// Expected information in bits
+    pub entropy: f64,
+    /// Whether the guess could itself be the secret
+    pub is_candidate: bool,
+}
+
+/// Finds the words consistent with the feedback so far and ranks the next guesses
+pub struct Solver {
+    /// Normalized spellings, each only once
+    words: Vec<String>,
+    letters: Vec<Vec<char>>,
+}
+
+impl Solver {
+    /// Creates a solver over the allowed guesses, usually `WordService::get_spellings_by_length`.
+    /// Words spelled the same once normalized are one guess.
+    pub fn new(spellings: &[Spelling]) -> Self {
+        let mut seen = HashSet::new();
+        let letters: Vec<Vec<char>> = spellings
+            .iter()
+            .filter(|spelling| seen.insert(&spelling.letters))
+            .map(|spelling| spelling.letters.clone())
+            .collect();
+        let words = letters.iter().map(|l| l.iter().collect()).collect();
+        Self { words, letters }
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Indices of the words that are consistent with every observation
+    fn candidate_indices(&self, observations: &[Observation]) -> Vec<usize> {
+        let constraints = Constraints::new(observations);
+        (0..self.words.len())
+            .filter(|&i| constraints.matches(&self.letters[i]))
+            .collect()
+    }
+
+    /// Words that could still be the secret
+    pub fn candidates(&self, observations: &[Observation]) -> Vec<&str> {
+        self.candidate_indices(observations)
+            .into_iter()
+            .map(|i| self.words[i].as_str())
+            .collect()
+    }
+
+    /// Expected information of `guess` in bits, when the secret is uniformly one of `candidates`
+    /// and the feedback is of the given kind
+    pub fn entropy(&self, guess: &[char], candidates: &[usize], kind: FeedbackKind) -> f64 {
+        let mut buckets: HashMap<u32, usize> = HashMap::new();
+        for &i in candidates {
+            *buckets
+                .entry(kind.pattern(guess, &self.letters[i]))
+                .or_default() += 1;
+        }
+
+        let total = candidates.len() as f64;
+        buckets
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum::<f64>()
+            .abs()
+    }
+
+    /// Ranks every allowed guess by expected information, returning the `limit` best ones.
+    ///
+    /// Ties are broken in favour of guesses that could be the secret.
+    pub fn rank_guesses(&self, observations: &[Observation], limit: usize) -> Vec<RankedGuess> {
+        self.rank_guesses_for(observations, FeedbackKind::of(observations), limit)
+    }
+
+    /// Like `rank_guesses`, for feedback of the given kind, e.g. before the first guess of a
+    /// counts-only game
+    pub fn rank_guesses_for(
+        &self,
+        observations: &[Observation],
+        kind: FeedbackKind,
+        limit: usize,
+    ) -> Vec<RankedGuess> {
+        let candidates = self.candidate_indices(observations);
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+        if candidates.len() == 1 {
+            return vec![RankedGuess {
+                word: self.words[candidates[0]].clone(),
+                entropy: 0.0,
+                is_candidate: true,
+            }];
+        }
+
+        // Split the guesses among the available cores, the scoring is independent per guess
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = self.words.len().div_ceil(threads).max(1);
+        let mut ranked: Vec<RankedGuess> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.words.len())
+                .collect::<Vec<_>>()
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let chunk = chunk.to_vec();
+                    let candidates = &candidates;
+                    scope.spawn(move || {
+                        chunk
+                            .into_iter()
+                            .map(|i| RankedGuess {
+                                word: self.words[i].clone(),
+                                entropy: self.entropy(&self.letters[i], candidates, kind),
+                                is_candidate: candidates.binary_search(&i).is_ok(),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Solver thread panicked"))
+                .collect()
+        });
+
+        ranked.sort_by(|a, b| {
+            b.entropy
+                .total_cmp(&a.entropy)
+                .then(b.is_candidate.cmp(&a.is_candidate))
+                .then(a.word.cmp(&b.word))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+
+    /// Compares every guess of a game with the best guess at that point.
+    ///
+    /// `opener` is the best first guess for this kind of feedback, when already known, as it
+    /// is the slowest to compute.
+    pub fn analyze(
+        &self,
+        observations: &[Observation],
+        kind: FeedbackKind,
+        opener: Option<RankedGuess>,
+    ) -> Analysis {
+        let mut opener = opener;
+        let mut guesses = Vec::with_capacity(observations.len());
+
+        for (i, observation) in observations.iter().enumerate() {
+            let before = self.candidate_indices(&observations[..i]);
+            let after = self.candidate_indices(&observations[..=i]);
+
+            let best = match (i, opener.take()) {
+                (0, Some(opener)) => Some(opener),
+                _ => self
+                    .rank_guesses_for(&observations[..i], kind, 1)
+                    .into_iter()
+                    .next(),
+            };
+            let (best_guess, best_entropy) = best
+                .map(|best| (best.word, best.entropy))
+                .unwrap_or_default();
+
+            let information = if after.is_empty() {
+                0.0
+            } else {
+                (before.len() as f64 / after.len() as f64).log2()
+            };
+
+            guesses.push(GuessAnalysis {
+                guess: observation.guess.iter().collect(),
+                candidates_before: before.len(),
+                candidates_after: after.len(),
+                best_guess,
+                best_entropy,
+                entropy: self.entropy(&observation.guess, &before, kind),
+                information,
+            });
+        }
+
+        Analysis { guesses }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::language::Language;
+
+    fn word(word: &str) -> Spelling {
+        Spelling::new(word, &Language::english())
+    }
+
+    #[test]
+    fn counts_only_feedback_tells_fewer_secrets_apart() {
+        let solver = Solver::new(&[word("ACB"), word("BAC")]);
+        let guess: Vec<char> = "ABC".chars().collect();
+
+        // Different tiles, but one correct and two present letters for both
+        assert_eq!(solver.entropy(&guess, &[0, 1], FeedbackKind::Tiles), 1.0);
+        assert_eq!(solver.entropy(&guess, &[0, 1], FeedbackKind::Counts), 0.0);
+    }
+
+    #[test]
+    fn feedback_kind_follows_the_observations() {
+        let counts: Observation = "ABC=1/2".parse().unwrap();
+        let tiles: Observation = "ABC=gyy".parse().unwrap();
+
+        assert_eq!(FeedbackKind::of(&[counts]), FeedbackKind::Counts);
+        assert_eq!(FeedbackKind::of(&[tiles]), FeedbackKind::Tiles);
+        assert_eq!(FeedbackKind::of(&[]), FeedbackKind::Tiles);
+    }
+}
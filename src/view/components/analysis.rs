@@ -0,0 +1,38 @@
+use maud::{Markup, Render, html};
+
+use crate::model::analysis::Analysis;
+
+impl Render for Analysis {
+    fn render(&self) -> Markup {
+        html! {
+            h6 { "Analysis" }
+            p { "Overall skill: "(self.skill())"/100" }
+            table .stripes .small-space {
+                thead {
+                    tr {
+                        th { "Guess" }
+                        th { "Words left" }
+                        th { "Best guess" }
+                        th { "Skill" }
+                        th { "Luck" }
+                    }
+                }
+                tbody {
+                    @for guess in &self.guesses {
+                        tr {
+                            td { (guess.guess) }
+                            td { (guess.candidates_before)" → "(guess.candidates_after) }
+                            td title={(format!("{:.2}", guess.best_entropy))" bits expected"} {
+                                (guess.best_guess)
+                            }
+                            td title={(format!("{:.2}", guess.entropy))" bits expected"} {
+                                (guess.skill())
+                            }
+                            td { (format!("{:+.2}", guess.luck()))" bits" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use maud::{Markup, Render, html};
+
+use crate::model::archive::{ArchiveDay, DailyArchive};
+
+impl Render for ArchiveDay {
+    fn render(&self) -> Markup {
+        let (icon, title) = match &self.result {
+            Some(result) if result.won => ("check_circle", format!("Won in {}", result.tries)),
+            Some(_) => ("cancel", "Lost".to_string()),
+            None => ("radio_button_unchecked", "Not played".to_string()),
+        };
+        let (_, _, day) = self.date.ymd();
+
+        html! {
+            a .chip href={"/daily/"(self.date)} title=(title) {
+                i { (icon) }
+                span { (day) }
+                @if self.result.is_some_and(|result| !result.on_the_day) {
+                    i .small title="Played from the archive" { "history" }
+                }
+            }
+        }
+    }
+}
+
+impl Render for DailyArchive {
+    fn render(&self) -> Markup {
+        // Days grouped by year and month
+        let mut months: Vec<((i64, u32), Vec<&ArchiveDay>)> = Vec::new();
+        for day in &self.days {
+            let (year, month, _) = day.date.ymd();
+            match months.last_mut() {
+                Some((key, days)) if *key == (year, month) => days.push(day),
+                _ => months.push(((year, month), vec![day])),
+            }
+        }
+
+        html! {
+            div .padding {
+                nav {
+                    a .button href={"/daily/"(self.today)} { i { "today" } span { "Today's puzzle" } }
+                    p .max .right-align { "Current streak: "(self.streak) }
+                }
+                p .small-text { "Replaying a past puzzle doesn't change your streak." }
+                @for ((year, month), days) in &months {
+                    h6 { (format!("{year:04}-{month:02}")) }
+                    nav .wrap {
+                        @for day in days {
+                            (day)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
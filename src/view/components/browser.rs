@@ -0,0 +1,103 @@
+use maud::{Markup, Render, html};
+
+use crate::{
+    model::browser::{DictionaryBrowser, WordDetail, word_url},
+    service::search::WordQuery,
+};
+
+impl DictionaryBrowser {
+    /// Link to another page of the same search
+    fn page_link(&self, page: usize) -> String {
+        let query = WordQuery {
+            page,
+            ..self.query.clone()
+        };
+        format!(
+            "/dictionary?{}",
+            serde_urlencoded::to_string(&query).unwrap_or_default()
+        )
+    }
+}
+
+impl Render for DictionaryBrowser {
+    fn render(&self) -> Markup {
+        let pages = self.results.total.div_ceil(self.results.per_page).max(1);
+
+        html! {
+            form action="/dictionary" method="get" .padding {
+                div .grid {
+                    div .s12 .m4 .field .label .border {
+                        input name="prefix" value=[self.query.prefix.as_deref()];
+                        label { "Starts with" }
+                    }
+                    div .s6 .m4 .field .label .border {
+                        input name="length" type="number" min="1" value=[self.query.length];
+                        label { "Length" }
+                    }
+                    div .s6 .m4 .field .label .border {
+                        input name="pattern" value=[self.query.pattern.as_deref()];
+                        label { "Pattern (e.g. ?A??E)" }
+                    }
+                }
+                button type="submit" { i { "search" } span { "Search" } }
+            }
+
+            p { (self.results.total)" word(s)" }
+            nav .wrap {
+                @for word in &self.results.words {
+                    a .chip href={"/dictionary/"(word.word)} { (word.word) }
+                }
+            }
+
+            nav .center-align .padding {
+                @if self.results.page > 1 {
+                    a .button .border href=(self.page_link(self.results.page - 1)) { i { "chevron_left" } }
+                }
+                span { "Page "(self.results.page)" of "(pages) }
+                @if self.results.page < pages {
+                    a .button .border href=(self.page_link(self.results.page + 1)) { i { "chevron_right" } }
+                }
+            }
+        }
+    }
+}
+
+impl Render for WordDetail {
+    fn render(&self) -> Markup {
+        html! {
+            article .padding {
+                h3 { (self.word.word) }
+                p .small-text {
+                    (self.word.word.chars().count())" letters, used "(self.word.frequency)" time(s) in other definitions"
+                    @if let Some(commonness) = self.word.commonness {
+                        ", more common than "((commonness * 100.0).round())"% of the words in the frequency list"
+                    }
+                }
+
+                h6 { "As a secret word" }
+                p {
+                    "Picked "(self.usage.picked)" time(s), won "(self.usage.won)", lost "(self.usage.lost)
+                    @if let Some(win_rate) = self.usage.win_rate() {
+                        " ("(win_rate)"% won)"
+                    }
+                }
+
+                h6 { "Definitions" }
+                ol {
+                    @for definition in &self.word.definitions {
+                        li { (definition) }
+                    }
+                }
+
+                @if !self.related.is_empty() {
+                    h6 { "Related words" }
+                    nav .wrap {
+                        @for related in &self.related {
+                            a .chip href=(word_url(related, &self.language)) { (related) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
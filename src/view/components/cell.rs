@@ -8,11 +8,17 @@ impl Render for Cell {
             CellState::Correct => "green",
             CellState::Absent => "red",
             CellState::Present => "yellow",
+            _ if self.is_hinted => "lightblue",
             _ => "",
         };
+        let hint_border = if self.is_hinted {
+            "border: 2px dashed blue; "
+        } else {
+            ""
+        };
 
         html! {
-            div .field .border .small .fill {
+            div .field .border .small .fill style="position: relative;" {
                 input
                     name="input[]"
                     type="text"
@@ -21,8 +27,13 @@ impl Render for Cell {
                     maxlength="1"
                     required
                     disabled[self.is_disabled]
-                    style={"flex: 1; max-width: 60px; text-align: center; background-color: "(cell_color)"; font-weight: bolder; font-size: 1.5rem; font-family: monospace"}
-                    oninput="this.value = this.value.toUpperCase().replace(/[^a-z]/gi, '');";
+                    style={"flex: 1; max-width: 60px; text-align: center; background-color: "(cell_color)"; "(hint_border)"font-weight: bolder; font-size: 1.5rem; font-family: monospace"}
+                    oninput="const grid = this.closest('[data-alphabet]'); const alphabet = grid.dataset.alphabet; const folding = JSON.parse(grid.dataset.caseFolding || '{}'); const upper = c => { const u = c.toUpperCase(); return folding[c] ?? ([...u].length === 1 ? u : c); }; this.value = [...this.value].map(upper).filter(c => alphabet.includes(c)).join('');";
+                @if let Some(occurrences) = self.occurrences {
+                    span .badge .circle .min title={"Occurs "(occurrences)" times in the word"} {
+                        (occurrences)
+                    }
+                }
             }
         }
     }
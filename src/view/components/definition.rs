@@ -0,0 +1,30 @@
+use maud::{Markup, Render, html};
+
+use crate::service::definition::Definition;
+
+impl Render for Definition {
+    fn render(&self) -> Markup {
+        html! {
+            @if let Some(term) = &self.term {
+                strong { (term) } " "
+            }
+            @if let Some(part_of_speech) = self.part_of_speech {
+                i { (part_of_speech) } " "
+            }
+            @if let Some(domain) = &self.domain {
+                span .small-text { "("(domain)") " }
+            }
+            @for label in &self.labels {
+                span .small-text { "["(label)"] " }
+            }
+            (self.sense)
+            @if !self.examples.is_empty() {
+                ul .small-text {
+                    @for example in &self.examples {
+                        li { i { (example) } }
+                    }
+                }
+            }
+        }
+    }
+}
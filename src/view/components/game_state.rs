@@ -1,18 +1,97 @@
 use maud::{Markup, Render, html};
 
-use crate::model::{game_state::GameState, message::Message};
+use crate::model::{
+    game_state::GameState,
+    hint::{DEFINITION_HINT_COST, Hint, LETTER_HINT_COST},
+    message::Message,
+};
+
+/// Longest list of remaining words shown at the end of a game
+const MAX_REVEALED_WORDS: usize = 200;
+
+impl GameState {
+    fn render_remaining(&self, remaining: &[String]) -> Markup {
+        html! {
+            p .small-text { (remaining.len())" possible word(s) left" }
+            @if self.is_game_over() && self.settings.reveal_remaining && !remaining.is_empty() {
+                details {
+                    summary { "Show them" }
+                    p { (remaining.iter().take(MAX_REVEALED_WORDS).cloned().collect::<Vec<_>>().join(", ")) }
+                }
+            }
+        }
+    }
+
+    fn render_hints(&self) -> Markup {
+        let disabled = self.is_game_over() || self.hints_left() == 0;
+
+        html! {
+            div #hints-container {
+                nav .center-align {
+                    button ws-send="hint" hx-vals="{\"hint\": \"letter\"}" disabled[disabled] .border .small-round {
+                        i { "abc" }
+                        span { "Reveal a letter (-"(LETTER_HINT_COST)")" }
+                    }
+                    button ws-send="hint" hx-vals="{\"hint\": \"definition\"}" disabled[disabled] .border .small-round {
+                        i { "menu_book" }
+                        span { "Show a clue (-"(DEFINITION_HINT_COST)")" }
+                    }
+                }
+                p .small-text { (self.hints_left())" hint(s) left" }
+                @for hint in &self.hints {
+                    @if let Hint::Definition(clue) = hint {
+                        blockquote .left-align { (clue) }
+                    }
+                }
+            }
+        }
+    }
+}
 
 impl Render for GameState {
     fn render(&self) -> Markup {
+        // Letters the language uppercases its own way, applied to what players type
+        let case_folding = serde_json::to_string(&self.language.case_folding).unwrap_or_default();
+
         html! {
-            div #grid-container .center-align style="max-width: 500px; margin: auto; padding: 1rem;" {
+            div #grid-container .center-align data-alphabet=(self.language.alphabet.iter().collect::<String>()) data-case-folding=(case_folding) style="max-width: 500px; margin: auto; padding: 1rem;" {
+                @if let Some(date) = self.daily {
+                    h6 { "Daily puzzle of "(date) }
+                    @if let Some(theme) = &self.theme {
+                        p { "Theme: "(theme) }
+                    }
+                    @if let Some(hint) = &self.daily_hint {
+                        blockquote .left-align { (hint) }
+                    }
+                }
+                @if let Some(clue) = &self.clue {
+                    article .border .left-align {
+                        h6 { "Definition" }
+                        p { (clue) }
+                    }
+                }
                 (self.grid)
+                @if let Some(remaining) = &self.remaining_words {
+                    (self.render_remaining(remaining))
+                }
+                @if self.settings.max_hints > 0 {
+                    br;
+                    (self.render_hints())
+                }
                 br;
                 div id="message-container" {
-                    (Message::new(self.status.clone(), self.secret_word.clone()))
+                    (Message::new(self.status.clone(), self.secret_word.clone(), self.language.id.clone(), self.score())
+                        .with_analysis(self.analysis.clone()))
                 }
+                // Filled with the statistics dialog when the player opens it
+                div #stats-container {}
             }
             div .padding .absolute .bottom .right {
+                @if self.player.is_some() {
+                    button ws-send="stats" hx-vals="{\"stats\": \"show\"}" .border .square .round title="Statistics" {
+                        i { "leaderboard" }
+                    }
+                }
                 button ws-send="reset" hx-vals="{\"reset\": \"reset\"}" hx-swap-oob="true" hx-target="grid-container" .extend .square .round  {
                     i { "replay" }
                     span { "Replay" }
@@ -1,6 +1,6 @@
 use maud::{Markup, Render, html};
 
-use crate::model::{game_state::GameStatus, message::Message};
+use crate::model::{browser::word_url, game_state::GameStatus, message::Message};
 
 impl Render for Message {
     fn render(&self) -> Markup {
@@ -10,21 +10,38 @@ impl Render for Message {
                     @if let GameStatus::Won | GameStatus::Lost = self.status {
                         @if self.status == GameStatus::Won {
                             h5 {"You've won"}
+                            p {"Score: "(self.score)}
                         } @else {
                             h5 {"You've lost!"}
-                            br;
-                            p {"The secret word is: "(self.secret_word)}
                         }
                         br;
+                        // The secret always links to its page, with a summary when it has one
                         p {
-                            {"Definitions of "(self.secret_word.word)": "}
-                            br;
-                            ul {
-                                @for meaning in &self.secret_word.meanings {
-                                    li { (meaning) }
+                            @if self.status == GameStatus::Lost {
+                                "The secret word is: "
+                            }
+                            a .link href=(word_url(&self.secret_word.word, &self.language)) target="_blank" { (self.secret_word) }
+                            @if let Some(primary) = self.secret_word.definitions.first() {
+                                ": "(primary.summary())
+                            }
+                        }
+                        @if !self.secret_word.definitions.is_empty() {
+                            details {
+                                summary .link { "Full entry" }
+                                ol {
+                                    @for definition in &self.secret_word.definitions {
+                                        li { (definition) }
+                                    }
                                 }
                             }
                         }
+                        div #analysis-container {
+                            @if let Some(analysis) = &self.analysis {
+                                (analysis)
+                            } @else {
+                                p .small-text { "Analysing your game..." }
+                            }
+                        }
                     }
                 }
             }
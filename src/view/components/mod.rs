@@ -1,5 +1,13 @@
+pub mod analysis;
+pub mod archive;
+pub mod browser;
 pub mod cell;
+pub mod definition;
 pub mod game_state;
 pub mod grid;
 pub mod message;
+pub mod pack;
 pub mod row;
+pub mod schedule;
+pub mod settings;
+pub mod stats;
@@ -0,0 +1,27 @@
+use maud::{Markup, Render, html};
+
+use crate::model::pack::PackPicker;
+
+impl Render for PackPicker {
+    fn render(&self) -> Markup {
+        if self.packs.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            nav .wrap .center-align .padding {
+                a .chip .fill[self.selected.is_none()] href="/" {
+                    i { "shuffle" }
+                    span { "Random words" }
+                }
+                @for pack in &self.packs {
+                    a .chip .fill[self.selected.as_deref() == Some(pack.id.as_str())]
+                        href={"/packs/"(pack.id)} title=[pack.theme.as_deref()] {
+                        i { "collections_bookmark" }
+                        span { (pack.name)" · "(pack.solved)"/"(pack.total) }
+                    }
+                }
+            }
+        }
+    }
+}
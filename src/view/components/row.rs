@@ -1,6 +1,6 @@
 use maud::{Markup, Render, html};
 
-use crate::model::row::Row;
+use crate::model::row::{Row, RowSummary};
 
 impl Render for Row {
     fn render(&self) -> Markup {
@@ -10,10 +10,26 @@ impl Render for Row {
                     (cell)
                 }
 
-                button ws-send="input" disabled[self.is_disabled()] .transparent .circle hx-include="input" hx-swap-oob="true" hx-target="grid-container" {
-                    i { "arrow_forward" }
+                @if let Some(summary) = &self.summary {
+                    (summary)
+                } @else {
+                    button ws-send="input" disabled[self.is_disabled()] .transparent .circle hx-include="input" hx-swap-oob="true" hx-target="grid-container" {
+                        i { "arrow_forward" }
+                    }
                 }
             }
         }
     }
 }
+
+impl Render for RowSummary {
+    fn render(&self) -> Markup {
+        html! {
+            div .chip .small title="Correct / present letters" {
+                span .green-text { (self.correct) }
+                span { "/" }
+                span .yellow-text { (self.present) }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use maud::{Markup, Render, html};
+
+use crate::model::schedule::SchedulePreview;
+
+impl Render for SchedulePreview {
+    fn render(&self) -> Markup {
+        html! {
+            div .padding {
+                table .stripes {
+                    thead {
+                        tr {
+                            th { "Day" }
+                            th { "Word" }
+                            th { "Theme" }
+                            th { "Hint" }
+                        }
+                    }
+                    tbody {
+                        @for day in &self.days {
+                            tr {
+                                td {
+                                    (day.date)
+                                    @if day.date == self.today { " (today)" }
+                                }
+                                td {
+                                    a .link href={"/dictionary/"(day.word)} { (day.word) }
+                                    @if !day.scheduled {
+                                        " "
+                                        i .small title="Picked from the dictionary, not scheduled" { "shuffle" }
+                                    }
+                                }
+                                td { (day.theme.as_deref().unwrap_or("")) }
+                                td { (day.hint.as_deref().unwrap_or("")) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use maud::{Markup, Render, html};
+
+use crate::model::settings::SettingsPanel;
+
+impl Render for SettingsPanel {
+    fn render(&self) -> Markup {
+        html! {
+            details #settings-panel .center-align open[self.error.is_some()] style="max-width: 500px; margin: auto;" {
+                summary .button .border .small-round {
+                    i { "tune" }
+                    span { "Settings" }
+                }
+                form ws-send .padding {
+                    div .grid {
+                        div .s6 .field .label .border {
+                            input name="word_length" type="number" required
+                                min=(self.bounds.word_lengths.start()) max=(self.bounds.word_lengths.end())
+                                value=(self.choice.word_length);
+                            label { "Word length" }
+                        }
+                        div .s6 .field .label .border {
+                            input name="num_tries" type="number" required
+                                min=(self.bounds.tries.start()) max=(self.bounds.tries.end())
+                                value=(self.choice.num_tries);
+                            label { "Tries" }
+                        }
+                        div .s6 .field .label .suffix .border {
+                            select name="mode" {
+                                @for (id, name) in &self.modes {
+                                    option value=(id) selected[*id == self.choice.mode] { (name) }
+                                }
+                            }
+                            label { "Mode" }
+                            i { "arrow_drop_down" }
+                        }
+                        div .s6 .field .label .suffix .border {
+                            select name="language" {
+                                @for (id, name) in &self.languages {
+                                    option value=(id) selected[*id == self.choice.language] { (name) }
+                                }
+                            }
+                            label { "Dictionary" }
+                            i { "arrow_drop_down" }
+                        }
+                    }
+                    @if let Some(error) = &self.error {
+                        p .error-text { (error) }
+                    }
+                    button type="submit" { i { "play_arrow" } span { "New game" } }
+                }
+            }
+        }
+    }
+}
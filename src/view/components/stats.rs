@@ -0,0 +1,54 @@
+use maud::{Markup, Render, html};
+
+use crate::model::stats::StatsDialog;
+
+impl StatsDialog {
+    fn render_distribution(&self) -> Markup {
+        let distribution = &self.stats.distribution;
+        let last_row = distribution.keys().last().copied().unwrap_or(0);
+        let most = distribution.values().max().copied().unwrap_or(1);
+
+        html! {
+            h6 { "Guess distribution" }
+            @if distribution.is_empty() {
+                p .small-text { "No games won yet" }
+            }
+            @for row in 1..=last_row {
+                @let count = distribution.get(&row).copied().unwrap_or(0);
+                @let width = (count * 100 / most).max(8);
+                div .row .small-space {
+                    span style="width: 2rem;" { (row) }
+                    div .max {
+                        div .small-round .right-align .small-padding
+                            .primary[self.highlight == Some(row)] .secondary-container[self.highlight != Some(row)]
+                            style={"width: "(width)"%;"} { (count) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Render for StatsDialog {
+    fn render(&self) -> Markup {
+        let stats = &self.stats;
+
+        html! {
+            dialog #stats-dialog .active open {
+                h5 { "Statistics" }
+                div .grid .center-align {
+                    div .s3 { h5 { (stats.played) } p .small-text { "Played" } }
+                    div .s3 { h5 { (stats.win_percentage()) } p .small-text { "Win %" } }
+                    div .s3 { h5 { (stats.current_streak) } p .small-text { "Current streak" } }
+                    div .s3 { h5 { (stats.max_streak) } p .small-text { "Max streak" } }
+                }
+                (self.render_distribution())
+                nav .right-align {
+                    button .border onclick="const dialog = this.closest('dialog'); dialog.classList.remove('active'); dialog.close();" {
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
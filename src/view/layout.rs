@@ -3,11 +3,29 @@ use maud::{DOCTYPE, Markup, Render, html};
 pub struct Layout {
     markup: Markup,
     title: String,
+    /// Game socket opened by the page, if any
+    socket: Option<String>,
 }
 
 impl Layout {
     pub fn new(markup: Markup, title: String) -> Self {
-        Self { markup, title }
+        Self {
+            markup,
+            title,
+            socket: Some("/ws".into()),
+        }
+    }
+
+    /// For pages that don't host a game
+    pub fn without_socket(mut self) -> Self {
+        self.socket = None;
+        self
+    }
+
+    /// For pages whose game is set up by the socket URL, e.g. a daily puzzle
+    pub fn with_socket(mut self, url: String) -> Self {
+        self.socket = Some(url);
+        self
     }
 
     fn head(&self) -> Markup {
@@ -32,11 +50,24 @@ impl Layout {
         html! {
             header .primary {
                 nav {
+                    a href="/" .button .circle .transparent title="Play" { i { "home" } }
                     h4 .max .center-align { (self.title) }
+                    a href="/daily" .button .circle .transparent title="Daily puzzle" { i { "today" } }
+                    a href="/archive" .button .circle .transparent title="Archive" { i { "history" } }
+                    a href="/dictionary" .button .circle .transparent title="Dictionary" { i { "menu_book" } }
                 }
             }
         }
     }
+
+    fn body(&self) -> Markup {
+        html! {
+            (self.appbar())
+            main .responsive .container {
+                (self.markup)
+            }
+        }
+    }
 }
 
 impl Render for Layout {
@@ -47,10 +78,13 @@ impl Render for Layout {
                 head {
                     (self.head())
                 }
-                body hx-ext="ws" ws-connect="/ws" {
-                    (self.appbar())
-                    main .responsive .container {
-                        (self.markup)
+                @if let Some(socket) = &self.socket {
+                    body hx-ext="ws" ws-connect=(socket) {
+                        (self.body())
+                    }
+                } @else {
+                    body {
+                        (self.body())
                     }
                 }
             }